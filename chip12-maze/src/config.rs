@@ -0,0 +1,33 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//マップ縦横幅の下限と上限(Grid、外壁含む)
+pub const MAP_GRIDS_MIN: i32 = 5;
+pub const MAP_GRIDS_MAX: i32 = 500;
+
+//ゴールが最低歩数を満たすまで迷路を作り直す回数の上限
+pub const MAP_GOAL_MAX_RETRY: u32 = 10;
+
+//難しさの段階の境目(点数0.0～100.0)
+pub const DIFFICULTY_MEDIUM_MIN: f32 = 40.0;
+pub const DIFFICULTY_HARD_MIN  : f32 = 60.0;
+
+//難しさを指定して迷路を作る時にシードを変えて試す回数の上限と、
+//アルゴリズムも変えてよい場合に試すループ化の割合[%]
+pub const MAP_DIFFICULTY_MAX_RETRY: u32 = 100;
+pub const DIFFICULTY_BRAID_PERCENTS: [ u32; 3 ] = [ 0, 50, 100 ];
+
+//ダンジョンの部屋の数と縦横幅(Grid、奇数に切り上げる)、一つの部屋を置く場所を探す回数の上限
+pub const DUNGEON_ROOM_COUNT: RangeInclusive<i32> = 6..=16;
+pub const DUNGEON_ROOM_SIZE : RangeInclusive<i32> = 3..=11;
+pub const DUNGEON_ROOM_MAX_RETRY: u32 = 50;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//四方の配列
+pub const NEWS: [ News; 4 ] = [ News::South, News::East, News::West, News::North ];
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
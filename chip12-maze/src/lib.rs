@@ -0,0 +1,25 @@
+//迷路の作成・経路探索・保存と読込を行うライブラリ
+//bevyに依存しないので、ゲーム以外のツールやテストからも使える
+//（座標はglamのIVec2。bevyのIVec2と同じ型）
+
+//external crates
+pub use glam::IVec2;
+use glam::{ Vec2, Vec3 };
+use counted_array::counted_array;
+use rand_core::RngCore;
+
+//standard library
+use std::ops::{ Range, RangeInclusive, Add };
+use std::f32::consts::PI;
+
+//internal submodules
+mod config;
+pub use config::*;
+
+mod types;
+pub use types::*;
+
+mod map;
+pub use map::*;
+
+//End of code.
\ No newline at end of file
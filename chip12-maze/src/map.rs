@@ -0,0 +1,379 @@
+use super::*;
+use rand_pcg::Pcg32;
+
+//マスの地形とフラグ
+mod flag;
+pub use flag::{ Flag, Terrain, Overlay };
+
+//迷路作成アルゴリズム
+mod generator;
+pub use generator::*;
+
+//迷路のループ化
+mod braid;
+
+//経路探索
+mod solver;
+pub use solver::DistanceField;
+
+//壁と床のメッシュの頂点データ
+mod mesh;
+pub use mesh::*;
+
+//3Dモデルのエクスポート
+mod export;
+
+//GPUを使わない画像出力
+mod render;
+pub use render::RenderOptions;
+
+//迷路ファイル(ASCII形式と構造化した形式)
+mod ascii;
+mod file;
+pub use file::MapFormat;
+
+//迷路の検査と統計
+mod stats;
+pub use stats::MapStats;
+
+//迷路の難しさ
+mod difficulty;
+pub use difficulty::Difficulty;
+
+//迷路を一歩ずつ作る(作る過程を見せる)
+mod digger;
+pub use digger::{ Digger, DigStep };
+
+//単体テスト
+#[cfg( test )]
+mod tests;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路のマップ
+#[derive( Clone )]
+pub struct Map
+{   rng              : Pcg32,          //専用乱数発生器
+    seed             : u64,            //乱数のシード(迷路を作る度にrngを初期化する)
+    matrix           : Vec<Vec<Flag>>, //map
+    start            : IVec2,          //スタート位置
+    goal             : IVec2,          //ゴール位置(スタートから最も遠いマス)
+    goal_min_distance: u32,            //スタートからゴールまでの最低歩数(0なら制約なし)
+    generator        : usize,          //迷路作成アルゴリズム(GENERATORSの添字)
+    braid_percent    : u32,            //行き止りを解消する割合[%]
+    room_count       : RangeInclusive<i32>, //ダンジョンの部屋の数
+    room_size        : RangeInclusive<i32>, //ダンジョンの部屋の縦横幅(Grid、奇数に切り上げる)
+    width            : i32,            //マップの横幅(Grid、外壁含む)
+    height           : i32,            //マップの縦幅(Grid、外壁含む)
+    trace            : Option<Vec<DigStep>>, //地形とフラグの変化の記録(一歩ずつ作る間だけSome)
+    tuned_from       : Option<( usize, u32 )>, //難しさに合わせて変える前のアルゴリズムとループ化の割合
+}
+
+//下限を1以上にし、上限を下限以上にした範囲
+fn at_least_one( range: RangeInclusive<i32> ) -> RangeInclusive<i32>
+{   let ( lo, hi ) = range.into_inner();
+    let lo = lo.max( 1 );
+    lo..=hi.max( lo )
+}
+
+//シードから乱数発生器を作る(PCGのstreamは固定)
+const PCG32_STREAM: u64 = 0x0a02_bdbf_7bb3_c0a7;
+fn new_rng( seed: u64 ) -> Pcg32
+{   Pcg32::new( seed, PCG32_STREAM )
+}
+
+impl Map
+{   //縦横幅とシードを指定してMapを作る(迷路はまだ作らない)
+    pub fn new( width: i32, height: i32, seed: u64 ) -> Self
+    {   let mut map = Self
+        {   rng              : new_rng( seed ),
+            seed,
+            matrix           : Vec::new(),
+            start            : IVec2::default(),
+            goal             : IVec2::default(),
+            goal_min_distance: 0,
+            generator        : 0,
+            braid_percent    : 0,
+            room_count       : DUNGEON_ROOM_COUNT,
+            room_size        : DUNGEON_ROOM_SIZE,
+            width            : 0,
+            height           : 0,
+            trace            : None,
+            tuned_from       : None,
+        };
+        map.resize( width, height );
+
+        map
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド
+impl Map
+{   //縦横幅を変更する（matrixは未定義で埋められる）
+    pub fn resize( &mut self, width: i32, height: i32 )
+    {   self.width  = width .clamp( MAP_GRIDS_MIN, MAP_GRIDS_MAX );
+        self.height = height.clamp( MAP_GRIDS_MIN, MAP_GRIDS_MAX );
+
+        let cell = Flag::new( Terrain::Undef );
+        let column = vec![ cell  ; self.height as usize ];
+        self.matrix = vec![ column; self.width  as usize ];
+    }
+
+    //マップのレンジ（外壁含む）
+    fn x_range( &self ) -> Range<i32> { 0..self.width  }
+    fn y_range( &self ) -> Range<i32> { 0..self.height }
+
+    //外壁を含まないレンジ
+    fn x_range_inner( &self ) -> Range<i32> { 1..self.width  - 1 }
+    fn y_range_inner( &self ) -> Range<i32> { 1..self.height - 1 }
+
+    //全てのマス(x優先で左上から)
+    fn cells( &self ) -> impl Iterator<Item = IVec2> + '_
+    {   self.x_range().flat_map( | x | self.y_range().map( move | y | IVec2::new( x, y ) ) )
+    }
+
+    //スタート地点とゴール
+    pub fn start( &self ) -> IVec2 { self.start }
+    pub fn goal ( &self ) -> IVec2 { self.goal  }
+
+    //乱数のシード(変えたら迷路を作り直す)
+    pub fn seed( &self ) -> u64 { self.seed }
+    pub fn set_seed( &mut self, seed: u64 ) { self.seed = seed }
+
+    //迷路作成アルゴリズム(GENERATORSの添字。選び直したら難しさに合わせて変えた記録は消す)
+    pub fn generator( &self ) -> usize { self.generator }
+    pub fn set_generator( &mut self, generator: usize )
+    {   self.generator = generator % GENERATORS.len();
+        self.tuned_from = None;
+    }
+
+    //行き止りを解消する割合[%](選び直したら難しさに合わせて変えた記録は消す)
+    pub fn braid_percent( &self ) -> u32 { self.braid_percent }
+    pub fn set_braid_percent( &mut self, percent: u32 )
+    {   self.braid_percent = percent.min( 100 );
+        self.tuned_from = None;
+    }
+
+    //ダンジョンの部屋の数と縦横幅の範囲(下限は1、上限が下限より小さければ下限に揃える)
+    pub fn room_count( &self ) -> RangeInclusive<i32> { self.room_count.clone() }
+    pub fn room_size ( &self ) -> RangeInclusive<i32> { self.room_size .clone() }
+    pub fn set_room_count( &mut self, range: RangeInclusive<i32> ) { self.room_count = at_least_one( range ) }
+    pub fn set_room_size ( &mut self, range: RangeInclusive<i32> ) { self.room_size  = at_least_one( range ) }
+
+    //スタートからゴールまでの最低歩数(0なら制約なし)
+    pub fn goal_min_distance( &self ) -> u32 { self.goal_min_distance }
+    pub fn set_goal_min_distance( &mut self, distance: u32 ) { self.goal_min_distance = distance }
+
+    //縦横幅
+    pub fn width ( &self ) -> i32 { self.width  }
+    pub fn height( &self ) -> i32 { self.height }
+
+    //ユーティリティ
+    fn is_inside( &self, cell: IVec2 ) -> bool
+    {   self.x_range().contains( &cell.x ) &&
+        self.y_range().contains( &cell.y )
+    }
+    fn is_inside_inner( &self, cell: IVec2 ) -> bool
+    {   self.x_range_inner().contains( &cell.x ) &&
+        self.y_range_inner().contains( &cell.y )
+    }
+    fn matrix_mut( &mut self, IVec2 { x, y }: IVec2 ) -> &mut Flag
+    {   &mut self.matrix[ x as usize ][ y as usize ]
+    }
+    fn matrix( &self, IVec2 { x, y }: IVec2 ) -> &Flag
+    {   &self.matrix[ x as usize ][ y as usize ]
+    }
+
+    //全体を埋める
+    fn fill_walls( &mut self )
+    {   self.matrix.iter_mut().for_each
+        (   |column| column.fill( Flag::new( Terrain::Wall ) )
+        );
+    }
+
+    //指定の位置の地形を書き換える（フラグは残す）
+    fn set_space( &mut self, cell: IVec2 ) { self.set_terrain( cell, Terrain::Space, DigStep::Dig  ( cell ) ) }
+    fn set_wall ( &mut self, cell: IVec2 ) { self.set_terrain( cell, Terrain::Wall , DigStep::Fill ( cell ) ) }
+    fn set_terrain( &mut self, cell: IVec2, terrain: Terrain, step: DigStep )
+    {   if ! self.is_inside( cell ) { return }
+        if self.matrix( cell ).terrain() != terrain { self.record( step ) }
+        self.matrix_mut( cell ).set_terrain( terrain );
+    }
+
+    //指定の位置の地形に行き止りのフラグを付加する
+    fn add_flag_deadend( &mut self, cell: IVec2 )
+    {   if ! self.is_inside( cell ) { return }
+        if ! self.is_deadend( cell ) { self.record( DigStep::Backtrack ( cell ) ) }
+        self.set_overlay( cell, Overlay::DeadEnd );
+    }
+
+    //地形とフラグの変化を記録する(記録中だけ)
+    fn record( &mut self, step: DigStep )
+    {   if let Some ( trace ) = &mut self.trace { trace.push( step ) }
+    }
+
+    //指定の位置の地形・フラグを判定する
+    pub fn is_wall ( &self, cell: IVec2 ) -> bool { self.terrain( cell ) == Terrain::Wall  } //範囲外は壁
+    pub fn is_space( &self, cell: IVec2 ) -> bool { self.terrain( cell ) == Terrain::Space } //範囲外に空地はない
+    fn is_deadend( &self, cell: IVec2 ) -> bool
+    {   self.has_overlay( cell, Overlay::DeadEnd ) //範囲外に空地はない(＝行き止りもない)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（乱数）
+//＜注意＞ randの分布(gen_range等)は版やusizeの幅(WASM)で結果が変わり得るので使わない。
+//        同じシードなら、どの環境でも同じ迷路になるよう整数演算だけで値を作る
+impl Map
+{   //0..lenの一様乱数
+    fn random_index( &mut self, len: usize ) -> usize
+    {   //剰余の偏りを避けるため、端数の範囲に入った値は捨てる
+        let len = len as u64;
+        let zone = ( 1 << 32 ) - ( 1 << 32 ) % len;
+        loop
+        {   let value = self.rng.next_u32() as u64;
+            if value < zone { return ( value % len ) as usize }
+        }
+    }
+
+    //確率pでtrue
+    fn random_bool( &mut self, p: f64 ) -> bool
+    {   ( self.rng.next_u32() as f64 ) < p * ( 1u64 << 32 ) as f64
+    }
+
+    //スライスをシャッフルする(Fisher-Yates)
+    fn shuffle<T>( &mut self, slice: &mut [ T ] )
+    {   for i in ( 1..slice.len() ).rev()
+        {   let j = self.random_index( i + 1 );
+            slice.swap( i, j );
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（迷路作成）
+impl Map
+{   //穴掘りを一歩進め、次に掘る位置を返す(掘り終えたらNone)
+    fn dig_labyrinth( &mut self, cell: IVec2 ) -> Option<IVec2>
+    {   //四方の掘れる壁と戻り道を記録する
+        let mut digable_walls = Vec::new();
+        let mut backtrack = None;
+        for news in NEWS
+        {   let next = cell + news;
+
+            //外壁は掘れない
+            if ! self.is_inside_inner( next ) { continue }
+
+            //四方のグリッドを調べる
+            if self.is_wall( next ) && self.is_digable( next, news )
+            {   //壁であり且つ掘れるなら
+                digable_walls.push( next );
+            }
+            else if self.is_space( next ) && ! self.is_deadend( next )
+            {   //道であり且つ行止りのマーキングがないなら
+                backtrack = Some ( next );
+            }
+        }
+
+        if ! digable_walls.is_empty()
+        {   //掘れる壁が見つかったので、方向をランダムに決めて進む
+            let next = digable_walls[ self.random_index( digable_walls.len() ) ];
+            self.set_space( next );
+            return Some ( next )
+        }
+
+        //掘れる壁が見つからず、戻り道も見つからないなら迷路完成
+        let backtrack = backtrack?;
+
+        //現在位置に行き止まりをマークし、戻り路へ進む(後戻りする)
+        self.add_flag_deadend( cell );
+        Some ( backtrack )
+    }
+
+    //壁が掘れるか調べる
+    fn is_digable( &self, cell: IVec2, news: News ) -> bool
+    {    match news
+        {   News::North
+            if self.is_wall( cell + News::North + News::West )
+            && self.is_wall( cell + News::North              ) // 壁壁壁
+            && self.is_wall( cell + News::North + News::East ) // 壁？壁
+            && self.is_wall( cell + News::West               )
+            && self.is_wall( cell + News::East               )
+                => true,
+            News::West
+            if self.is_wall( cell + News::North + News::West )
+            && self.is_wall( cell + News::North              ) // 壁壁
+            && self.is_wall( cell + News::West               ) // 壁？◎
+            && self.is_wall( cell + News::South + News::West ) // 壁壁
+            && self.is_wall( cell + News::South              )
+                => true,
+            News::East
+            if self.is_wall( cell + News::North              )
+            && self.is_wall( cell + News::North + News::East ) // 　壁壁
+            && self.is_wall( cell + News::East               ) // ◎？壁
+            && self.is_wall( cell + News::South              ) // 　壁壁
+            && self.is_wall( cell + News::South + News::East )
+                => true,
+            News::South
+            if self.is_wall( cell + News::West               )
+            && self.is_wall( cell + News::East               ) // 　◎
+            && self.is_wall( cell + News::South + News::West ) // 壁？壁
+            && self.is_wall( cell + News::South              ) // 壁壁壁
+            && self.is_wall( cell + News::South + News::East )
+                => true,
+            _   => false,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（データ作成）
+impl Map
+{   //選択中のアルゴリズムで迷路を作り直す
+    //（ゴールの最低歩数を満たせなかった場合はfalseを返す。迷路は最後に作ったもの）
+    pub fn generate( &mut self ) -> bool
+    {   let digger = self.digger();
+        digger.finish( self )
+    }
+
+    //作り始めの迷路(壁で埋めてスタート地点だけ空地。ゴールはまだない)にして、
+    //選択中のアルゴリズムで一歩ずつ作る状態を用意する
+    //（作り直す場合は空地を一つずつ埋め戻す。一歩ずつ作る時はその手順も記録される）
+    fn start_maze( &mut self ) -> Box<dyn GeneratorState>
+    {   let spaces: Vec<_> = self.cells().filter( | &cell | self.is_space( cell ) ).collect();
+        spaces.into_iter().for_each( | cell | self.set_wall( cell ) );
+        self.fill_walls();
+
+        //スタート地点を決める
+        self.start = IVec2::new( self.width / 2, self.height / 2 );
+        self.goal = self.start;
+        let start = self.start;
+        self.set_space( start );
+
+        GENERATORS[ self.generator ].start( self )
+    }
+
+    //スタートから最も遠いマスにゴールを置き、その歩数を返す
+    fn place_goal( &mut self ) -> u32
+    {   let ( goal, distance ) = self.distance_field( self.start )
+            .farthest()
+            .unwrap_or( ( self.start, 0 ) );
+        self.goal = goal;
+
+        distance
+    }
+
+    //選択中のアルゴリズムの名前
+    pub fn generator_name( &self ) -> &'static str
+    {   GENERATORS[ self.generator ].name()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,112 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//ASCII形式の迷路ファイル(手で書いてgitで差分を見られるように)
+//  seed: <N>        乱数のシード(省略時は0)
+//  start: <X> <Y>   スタート地点
+//  goal: <X> <Y>    ゴール(省略時はスタートから最も遠いマス)
+//  #########        ヘッダーの後に迷路(#は壁、.は空地。行の長さは揃える)
+//  #.......#
+//  #########
+const ASCII_WALL : char = '#';
+const ASCII_SPACE: char = '.';
+
+impl Map
+{   //ASCII形式の文字列にする
+    pub fn to_ascii( &self ) -> String
+    {   let mut text = format!
+        (   "seed: {}\nstart: {} {}\ngoal: {} {}\n",
+            self.seed, self.start.x, self.start.y, self.goal.x, self.goal.y
+        );
+        for y in self.y_range()
+        {   for x in self.x_range()
+            {   let is_wall = self.is_wall( IVec2::new( x, y ) );
+                text.push( if is_wall { ASCII_WALL } else { ASCII_SPACE } );
+            }
+            text.push( '\n' );
+        }
+
+        text
+    }
+
+    //ASCII形式の文字列からMapを作る(エラーは行番号付きのメッセージ)
+    pub fn from_ascii( text: &str ) -> Result<Map, String>
+    {   let mut seed  = 0;
+        let mut start = None;
+        let mut goal  = None;
+        let mut rows  = Vec::new();
+
+        for ( lineno, line ) in text.lines().enumerate().map( | ( i, line ) | ( i + 1, line.trim_end() ) )
+        {   if line.is_empty() { continue }
+
+            //迷路の行
+            if line.starts_with( [ ASCII_WALL, ASCII_SPACE ] )
+            {   rows.push( ( lineno, line ) );
+                continue;
+            }
+            if ! rows.is_empty() { return Err ( format!( "Line {lineno}: Header after the grid" ) ) }
+
+            //ヘッダーの行
+            let error = || format!( "Line {lineno}: Invalid header \"{line}\"" );
+            let ( key, value ) = line.split_once( ':' ).ok_or_else( error )?;
+            match key.trim()
+            {   "seed"  => seed  = value.trim().parse().map_err( | _ | error() )?,
+                "start" => start = Some ( parse_cell( value ).ok_or_else( error )? ),
+                "goal"  => goal  = Some ( parse_cell( value ).ok_or_else( error )? ),
+                _       => return Err ( error() ),
+            }
+        }
+
+        //縦横幅
+        let Some ( &( _, first ) ) = rows.first() else { return Err ( "No grid".to_string() ) };
+        let width  = first.chars().count() as i32;
+        let height = rows.len() as i32;
+        let range = MAP_GRIDS_MIN..=MAP_GRIDS_MAX;
+        if ! range.contains( &width ) || ! range.contains( &height )
+        {   return Err ( format!( "Grid size {width}x{height} is out of range {range:?}" ) )
+        }
+
+        //迷路
+        let mut map = Map::new( width, height, seed );
+        map.fill_walls();
+        for ( y, &( lineno, line ) ) in rows.iter().enumerate()
+        {   if line.chars().count() as i32 != width
+            {   return Err ( format!( "Line {lineno}: Row length differs from the first row" ) )
+            }
+            for ( x, char ) in line.chars().enumerate()
+            {   match char
+                {   ASCII_WALL  => (),
+                    ASCII_SPACE => map.set_space( IVec2::new( x as i32, y as i32 ) ),
+                    _ => return Err ( format!( "Line {lineno}: Invalid character '{char}'" ) ),
+                }
+            }
+        }
+
+        //スタート地点とゴール(空地であること)
+        let check = | map: &Map, name, cell: IVec2 |
+        {   if map.is_space( cell ) { Ok ( cell ) }
+            else { Err ( format!( "{name} ({}, {}) is not a space", cell.x, cell.y ) ) }
+        };
+        map.start = check( &map, "Start", start.ok_or( "Start is missing" )? )?;
+        match goal
+        {   Some ( goal ) => map.goal = check( &map, "Goal", goal )?,
+            None => { map.place_goal(); },
+        }
+
+        Ok ( map )
+    }
+}
+
+//"<X> <Y>"を座標にする
+fn parse_cell( value: &str ) -> Option<IVec2>
+{   let mut numbers = value.split_whitespace().map( | n | n.parse::<i32>().ok() );
+    let x = numbers.next()??;
+    let y = numbers.next()??;
+    if numbers.next().is_some() { return None }
+    Some ( IVec2::new( x, y ) )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,94 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（迷路のループ化）
+impl Map
+{   //行き止りを指定の割合[%]だけ壁を掘って解消し、迷路にループを作る
+    //どの向きに掘っても2x2の広場ができる行き止りは埋めて、一つ手前のマスで解消を続ける
+    //＜注意＞ 外壁は掘らない。スタート地点は埋めない
+    pub(super) fn braid( &mut self, percent: u32 )
+    {   //行き止りを集めてシャッフルする
+        let mut deadends: Vec<_> = self.inner_cells().filter( | &cell | self.is_dead_end_shape( cell ) ).collect();
+        self.shuffle( &mut deadends );
+
+        //残す数(見積もらず、地形を変えるたびに実際に残っている数を数える)
+        let goal = deadends.len() - deadends.len() * percent.min( 100 ) as usize / 100;
+        let mut remaining = deadends.len();
+
+        //地形が変わって行き止りが増減するのは、そのマスと四方だけ
+        let count_around = | map: &Self, center: IVec2 |
+            std::iter::once( center ).chain( NEWS.map( | news | center + news ) )
+            .filter( | &cell | map.is_dead_end_shape( cell ) )
+            .count();
+
+        for mut cell in deadends
+        {   //先に掘った壁で既に解消されていることがある
+            while remaining > goal && self.is_dead_end_shape( cell )
+            {   //隣の壁のうち、掘ると他の通路につながるものを探す(その通路が行き止りなら優先する)
+                let beyond = | wall: IVec2 | NEWS.map( | news | wall + news ).into_iter().filter( move | &next | next != cell );
+                let walls: Vec<_> = NEWS.iter()
+                    .map( | &news | cell + news )
+                    .filter( | &wall | self.is_braidable( wall ) )
+                    .filter( | &wall | beyond( wall ).any( | next | self.is_space( next ) ) )
+                    .collect();
+                let preferred: Vec<_> = walls.iter()
+                    .filter( | &&wall | beyond( wall ).any( | next | self.is_dead_end_shape( next ) ) )
+                    .copied()
+                    .collect();
+                let candidates = if preferred.is_empty() { walls } else { preferred };
+
+                //壁を掘る
+                if ! candidates.is_empty()
+                {   let wall = candidates[ self.random_index( candidates.len() ) ];
+                    let before = count_around( self, wall );
+                    self.set_space( wall );
+                    remaining = remaining + count_around( self, wall ) - before;
+                    break
+                }
+
+                //掘れないなら埋めて、一つ手前のマスで続ける
+                if cell == self.start { break }
+                let Some ( back ) = NEWS.map( | news | cell + news ).into_iter().find( | &next | self.is_space( next ) ) else { break };
+                let before = count_around( self, cell );
+                self.set_wall( cell );
+                remaining = remaining + count_around( self, cell ) - before;
+                cell = back;
+            }
+        }
+    }
+
+    //行き止りの形か？(四方のうち空地が一つだけの空地)
+    pub(super) fn is_dead_end_shape( &self, cell: IVec2 ) -> bool
+    {   self.is_space( cell )
+        && NEWS.iter().filter( | &&news | self.is_space( cell + news ) ).count() == 1
+    }
+
+    //行き止りの数
+    pub fn dead_end_count( &self ) -> usize
+    {   self.inner_cells().filter( | &cell | self.is_dead_end_shape( cell ) ).count()
+    }
+
+    //外壁を除く全てのマス
+    fn inner_cells( &self ) -> impl Iterator<Item = IVec2> + '_
+    {   self.x_range_inner().flat_map( | x | self.y_range_inner().map( move | y | IVec2::new( x, y ) ) )
+    }
+
+    //ループ化のために掘ってよい壁か？
+    fn is_braidable( &self, wall: IVec2 ) -> bool
+    {   //外壁は掘れない
+        if ! self.is_inside_inner( wall ) || ! self.is_wall( wall ) { return false }
+
+        //掘っても2x2の広場ができないこと(この壁を含む四つの2x2の中に、他の壁が残る)
+        [ IVec2::new( -1, -1 ), IVec2::new( 0, -1 ), IVec2::new( -1, 0 ), IVec2::ZERO ].iter().all
+        (   | &corner |
+            [ IVec2::ZERO, IVec2::X, IVec2::Y, IVec2::ONE ].iter()
+                .map( | &offset | wall + corner + offset )
+                .any( | next | next != wall && self.is_wall( next ) )
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
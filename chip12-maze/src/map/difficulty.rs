@@ -0,0 +1,110 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路の難しさの段階
+#[derive( Clone, Copy, PartialEq, Eq, Debug )]
+pub enum Difficulty { Easy, Medium, Hard }
+
+impl Difficulty
+{   //全ての段階(易しい順)
+    pub const ALL: [ Difficulty; 3 ] = [ Difficulty::Easy, Difficulty::Medium, Difficulty::Hard ];
+
+    //難しさの点数から段階を決める
+    pub fn from_score( score: f32 ) -> Self
+    {   if      score >= DIFFICULTY_HARD_MIN   { Difficulty::Hard   }
+        else if score >= DIFFICULTY_MEDIUM_MIN { Difficulty::Medium }
+        else { Difficulty::Easy }
+    }
+
+    //名前(コマンドライン引数とHUDで使う)
+    pub fn name( self ) -> &'static str
+    {   match self
+        {   Difficulty::Easy   => "EASY",
+            Difficulty::Medium => "MEDIUM",
+            Difficulty::Hard   => "HARD",
+        }
+    }
+
+    //名前から段階を探す(大文字小文字は区別しない)
+    pub fn find( name: &str ) -> Option<Self>
+    {   Self::ALL.into_iter().find( | level | level.name().eq_ignore_ascii_case( name ) )
+    }
+}
+
+impl std::fmt::Display for Difficulty
+{   fn fmt( &self, f: &mut std::fmt::Formatter ) -> std::fmt::Result
+    {   f.write_str( self.name() )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl MapStats
+{   //難しさの点数(0.0～100.0、ゴールに行けないならNone)
+    //解の経路上で道を選ぶ回数を迷路の広さで均し、行き止りが多い(ループが少ない)ほど重くする
+    pub fn difficulty( &self ) -> Option<f32>
+    {   let decisions = self.decisions? as f32;
+        let branches = ( self.junctions + self.crossroads ).max( 1 ) as f32;
+        let raw = decisions / ( self.reachable.max( 1 ) as f32 ).sqrt()
+            * ( 1.0 + self.dead_ends as f32 / branches );
+
+        Some ( 100.0 * raw / ( 1.0 + raw ) )
+    }
+
+    //難しさの段階
+    pub fn level( &self ) -> Option<Difficulty>
+    {   self.difficulty().map( Difficulty::from_score )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（難しさを指定した迷路作り）
+impl Map
+{   //指定の難しさになるまでシードだけを変えて迷路を作り直す
+    //（アルゴリズム、ループ化の割合、縦横幅は変えない。見つからなかった場合はfalseを返す。迷路は最後に作ったもの）
+    pub fn generate_with_difficulty( &mut self, level: Difficulty ) -> bool
+    {   for _ in 0..MAP_DIFFICULTY_MAX_RETRY
+        {   if self.generate() && self.stats().level() == Some ( level ) { return true }
+
+            //次のシード(同じシードから始めれば同じ結果になる)
+            self.seed = self.rng.next_u32() as u64;
+        }
+
+        false
+    }
+
+    //シードだけで見つからなければ、アルゴリズムとループ化の割合も変えて探す
+    //（変えた場合は元の設定をtuned_from()で返す。どちらも元の設定とシードから探し直す）
+    pub fn generate_with_difficulty_tuned( &mut self, level: Difficulty ) -> bool
+    {   let ( generator, braid_percent ) = self.tuned_from.take().unwrap_or( ( self.generator, self.braid_percent ) );
+        let seed = self.seed;
+
+        for i in 0..GENERATORS.len()
+        {   for braid in std::iter::once( braid_percent ).chain( DIFFICULTY_BRAID_PERCENTS )
+            {   self.generator = ( generator + i ) % GENERATORS.len();
+                self.braid_percent = braid;
+                self.seed = seed;
+                if ! self.generate_with_difficulty( level ) { continue }
+
+                let is_tuned = self.generator != generator || self.braid_percent != braid_percent;
+                if is_tuned { self.tuned_from = Some ( ( generator, braid_percent ) ) }
+                return true
+            }
+        }
+
+        //見つからなかったら元の設定に戻す
+        self.generator = generator;
+        self.braid_percent = braid_percent;
+        self.seed = seed;
+        self.generate_with_difficulty( level )
+    }
+
+    //難しさに合わせて変える前のアルゴリズムとループ化の割合(変えていなければNone)
+    pub fn tuned_from( &self ) -> Option<( usize, u32 )> { self.tuned_from }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
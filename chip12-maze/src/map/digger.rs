@@ -0,0 +1,99 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路を作る途中で変わったマス(一歩で複数のマスが変わることもある)
+#[derive( Clone, Copy, PartialEq, Eq, Debug )]
+pub enum DigStep
+{   Dig       ( IVec2 ), //壁を掘って空地にする
+    Fill      ( IVec2 ), //空地を壁にする(再帰分割法、ループ化、作り直す時の埋め戻し)
+    Backtrack ( IVec2 ), //行き止りの印を付けて後戻りする(穴掘り法)
+}
+
+impl DigStep
+{   //手順の対象のマス
+    pub fn cell( self ) -> IVec2
+    {   match self
+        {   DigStep::Dig ( cell ) | DigStep::Fill ( cell ) | DigStep::Backtrack ( cell ) => cell
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路を一歩ずつ作る(途中で止めて再開できる)
+//アルゴリズムの状態を一歩ずつ進め、終わったらループ化してゴールを置く
+//一度に作る場合も同じ手順を最後まで進めるので、同じ迷路ができる
+pub struct Digger
+{   state      : Box<dyn GeneratorState>, //アルゴリズムの途中の状態
+    attempts   : u32,             //迷路を作り終えた回数(ゴールが近すぎると作り直す)
+    head       : Option<DigStep>, //最後に当てはめた手順
+    is_finished: bool,            //ゴールまで置いたか？
+    is_goal_ok : bool,            //ゴールの最低歩数を満たせたか？
+}
+
+impl Map
+{   //現在の設定(縦横幅、シード、アルゴリズム等)で迷路を一歩ずつ作り始める
+    //Mapは作り始めの迷路(壁で埋めてスタート地点だけ空地。ゴールはまだない)になる
+    pub fn digger( &mut self ) -> Digger
+    {   //初期化する(同じシードなら同じ迷路になる)
+        self.rng = new_rng( self.seed );
+        let state = self.start_maze();
+
+        Digger { state, attempts: 0, head: None, is_finished: false, is_goal_ok: false }
+    }
+}
+
+impl Digger
+{   //一歩進めて、変わったマスの手順を返す(何も変わらない一歩もある。作り終えていたらNone)
+    pub fn step( &mut self, map: &mut Map ) -> Option<Vec<DigStep>>
+    {   map.trace = Some ( Vec::new() );
+        let is_stepped = self.advance( map );
+        let steps = map.trace.take().unwrap_or_default();
+        if let Some ( &step ) = steps.last() { self.head = Some ( step ) }
+
+        is_stepped.then_some( steps )
+    }
+
+    //最後まで進めて迷路を完成させる(ゴールの最低歩数を満たせなかった場合はfalse)
+    pub fn finish( mut self, map: &mut Map ) -> bool
+    {   while self.advance( map ) {}
+        self.is_goal_ok
+    }
+
+    //アルゴリズムを一歩進める。作り終えたらループ化してゴールを置き、近すぎれば作り直す
+    //（ゴールまで置いた後は何もせずfalse）
+    fn advance( &mut self, map: &mut Map ) -> bool
+    {   if self.is_finished { return false }
+        if self.state.step( map ) { return true }
+
+        //行き止りを解消してループを作る
+        let percent = map.braid_percent;
+        if percent > 0 { map.braid( percent ) }
+
+        //ゴールが近すぎる場合は乱数を進めて作り直す
+        self.attempts += 1;
+        self.is_goal_ok = map.place_goal() >= map.goal_min_distance;
+        if self.is_goal_ok || self.attempts >= MAP_GOAL_MAX_RETRY
+        {   self.is_finished = true;
+        }
+        else
+        {   self.state = map.start_maze();
+        }
+
+        true
+    }
+
+    //最後に当てはめた手順(掘っている先頭)
+    pub fn head( &self ) -> Option<DigStep> { self.head }
+
+    //ゴールまで置いたか？
+    pub fn is_finished( &self ) -> bool { self.is_finished }
+
+    //ゴールの最低歩数を満たせたか？(作り終えるまではfalse)
+    pub fn is_goal_ok( &self ) -> bool { self.is_goal_ok }
+}
+
+////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,53 @@
+use super::*;
+use std::fmt::Write;
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Map
+{   //壁と床をWavefront OBJ形式にする(3D表示と同じ頂点データ、マテリアルは別の.mtlファイル)
+    //戻り値は(.objの中身、.mtlの中身)
+    pub fn export_obj( &self, palette: &Palette, mtl_fname: &str ) -> ( String, String )
+    {   //チャンクに分けずに部位ごとにまとめる
+        let mut walls  = MeshData::default();
+        let mut floors = MeshData::default();
+        for chunk in self.wall_chunk_iter()
+        {   walls .append( self.wall_chunk_data ( chunk ) );
+            floors.append( self.floor_chunk_data( chunk ) );
+        }
+        let parts =
+        [   ( "wall"     , walls                , palette.wall      ),
+            ( "wall_zero", self.wall_zero_data(), palette.wall_zero ),
+            ( "floor"    , floors               , palette.floor     ),
+            ( "goal"     , self.goal_data()     , palette.goal      ),
+        ];
+
+        //OBJ(頂点番号は1から始まり、ファイル全体で通し番号)
+        let mut obj = String::new();
+        let mut mtl = String::new();
+        let _ = writeln!( obj, "# {}: seed {}, {}x{}", env!( "CARGO_PKG_NAME" ), self.seed, self.width, self.height );
+        let _ = writeln!( obj, "mtllib {mtl_fname}" );
+        let mut base = 1;
+        for ( name, data, color ) in parts
+        {   let _ = writeln!( obj, "o {name}\nusemtl {name}" );
+            data.positions.iter().for_each( | [ x, y, z ] | { let _ = writeln!( obj, "v {x} {y} {z}" ); } );
+            data.uvs      .iter().for_each( | [ u, v ]    | { let _ = writeln!( obj, "vt {u} {}", 1.0 - v ); } );
+            data.normals  .iter().for_each( | [ x, y, z ] | { let _ = writeln!( obj, "vn {x} {y} {z}" ); } );
+            for triangle in data.indices.chunks( 3 )
+            {   let [ a, b, c ] = [ 0, 1, 2 ].map( | i | triangle[ i ] as usize + base );
+                let _ = writeln!( obj, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}" );
+            }
+            base += data.positions.len();
+
+            //MTL(色だけ書く)
+            let [ r, g, b, _ ] = color.map( | c | c as f32 / 255.0 );
+            let _ = writeln!( mtl, "newmtl {name}\nKd {r:.4} {g:.4} {b:.4}\n" );
+        }
+
+        ( obj, mtl )
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,181 @@
+use super::*;
+use serde::{ Deserialize, Serialize };
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路ファイルの形式
+#[derive( Clone, Copy, PartialEq, Eq, Debug )]
+pub enum MapFormat { Ascii, Ron, Json }
+
+impl MapFormat
+{   //ファイル名の拡張子で決める(.ron、.json以外はASCII形式)
+    pub fn from_fname( fname: &str ) -> Self
+    {   let ext = std::path::Path::new( fname ).extension().and_then( | ext | ext.to_str() );
+        match ext.map( | ext | ext.to_ascii_lowercase() ).as_deref()
+        {   Some ( "ron"  ) => MapFormat::Ron,
+            Some ( "json" ) => MapFormat::Json,
+            _               => MapFormat::Ascii,
+        }
+    }
+
+    //保存するファイルの拡張子
+    pub fn extension( self ) -> &'static str
+    {   match self
+        {   MapFormat::Ascii => "txt",
+            MapFormat::Ron   => "ron",
+            MapFormat::Json  => "json",
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//構造化した迷路ファイル(RON/JSON)の版
+//形式を変えたら版を上げ、古い版は読込時に移行するかエラーにする
+//（版2でダンジョンの部屋の数と縦横幅を追加。版1は既定値で読む）
+const MAP_FILE_VERSION: u32 = 2;
+const MAP_FILE_VERSION_MIN: u32 = 1;
+
+//版だけを先に読む
+#[derive( Deserialize )]
+struct MapFileVersion { version: u32 }
+
+//構造化した迷路ファイルの中身(版2)
+//matrixは行(y)ごとに、マスのビット(Flag)を16進数で空白区切りに並べる(地形は下位2ビット、行き止りは0b100)
+#[derive( Serialize, Deserialize )]
+struct MapFile
+{   version          : u32,
+    seed             : u64,
+    width            : i32,
+    height           : i32,
+    start            : ( i32, i32 ),
+    goal             : ( i32, i32 ),
+    generator        : String, //アルゴリズム名(並び順が変わっても読めるように)
+    braid_percent    : u32,
+    goal_min_distance: u32,
+    #[serde( default = "default_room_count" )]
+    room_count       : ( i32, i32 ), //ダンジョンの部屋の数(下限、上限)
+    #[serde( default = "default_room_size" )]
+    room_size        : ( i32, i32 ), //ダンジョンの部屋の縦横幅(下限、上限)
+    matrix           : Vec<String>,
+}
+
+//版1のファイルにない値の既定値
+fn default_room_count() -> ( i32, i32 ) { DUNGEON_ROOM_COUNT.into_inner() }
+fn default_room_size () -> ( i32, i32 ) { DUNGEON_ROOM_SIZE .into_inner() }
+
+impl Map
+{   //指定の形式の文字列にする
+    pub fn serialize( &self, format: MapFormat ) -> Result<String, String>
+    {   match format
+        {   MapFormat::Ascii => Ok ( self.to_ascii() ),
+            MapFormat::Ron   =>
+                ron::ser::to_string_pretty( &self.to_map_file(), ron::ser::PrettyConfig::default() )
+                .map_err( | err | err.to_string() ),
+            MapFormat::Json  =>
+                serde_json::to_string_pretty( &self.to_map_file() )
+                .map_err( | err | err.to_string() ),
+        }
+    }
+
+    //指定の形式の文字列からMapを作る
+    pub fn deserialize( text: &str, format: MapFormat ) -> Result<Map, String>
+    {   //版を確かめてから中身を読む
+        let version: MapFileVersion = match format
+        {   MapFormat::Ascii => return Map::from_ascii( text ),
+            MapFormat::Ron   => ron::from_str( text ).map_err( | err | err.to_string() )?,
+            MapFormat::Json  => serde_json::from_str( text ).map_err( | err | err.to_string() )?,
+        };
+        match version.version
+        {   MAP_FILE_VERSION_MIN..=MAP_FILE_VERSION => (),
+            v if v > MAP_FILE_VERSION =>
+                return Err ( format!( "Map file version {v} is newer than supported version {MAP_FILE_VERSION}" ) ),
+            v =>
+                return Err ( format!( "Map file version {v} is no longer supported" ) ),
+        }
+
+        let file: MapFile = match format
+        {   MapFormat::Ron => ron::from_str( text ).map_err( | err | err.to_string() )?,
+            _              => serde_json::from_str( text ).map_err( | err | err.to_string() )?,
+        };
+        Map::from_map_file( file )
+    }
+
+    //保存用の構造体にする
+    fn to_map_file( &self ) -> MapFile
+    {   let matrix = self.y_range().map
+        (   | y |
+            self.x_range()
+            .map( | x | format!( "{:x}", self.matrix( IVec2::new( x, y ) ) ) )
+            .collect::<Vec<_>>()
+            .join( " " )
+        )
+        .collect();
+
+        MapFile
+        {   version          : MAP_FILE_VERSION,
+            seed             : self.seed,
+            width            : self.width,
+            height           : self.height,
+            start            : ( self.start.x, self.start.y ),
+            goal             : ( self.goal.x, self.goal.y ),
+            generator        : self.generator_name().to_string(),
+            braid_percent    : self.braid_percent,
+            goal_min_distance: self.goal_min_distance,
+            room_count       : self.room_count().into_inner(),
+            room_size        : self.room_size ().into_inner(),
+            matrix,
+        }
+    }
+
+    //保存用の構造体からMapを作る
+    fn from_map_file( file: MapFile ) -> Result<Map, String>
+    {   //縦横幅
+        let ( width, height ) = ( file.width, file.height );
+        let range = MAP_GRIDS_MIN..=MAP_GRIDS_MAX;
+        if ! range.contains( &width ) || ! range.contains( &height )
+        {   return Err ( format!( "Grid size {width}x{height} is out of range {range:?}" ) )
+        }
+        if file.matrix.len() != height as usize
+        {   return Err ( format!( "Matrix has {} rows, expected {height}", file.matrix.len() ) )
+        }
+
+        let mut map = Map::new( width, height, file.seed );
+        map.goal_min_distance = file.goal_min_distance;
+        map.braid_percent     = file.braid_percent;
+        map.set_room_count( file.room_count.0..=file.room_count.1 );
+        map.set_room_size ( file.room_size .0..=file.room_size .1 );
+        map.generator = GENERATORS.iter()
+            .position( | generator | generator.name() == file.generator )
+            .ok_or_else( || format!( "Unknown generator \"{}\"", file.generator ) )?;
+
+        //マス(地形は空地か壁のどちらか一つ。フラグのビットはそのまま残す)
+        for ( y, row ) in file.matrix.iter().enumerate()
+        {   let cells: Vec<_> = row.split_whitespace().collect();
+            if cells.len() != width as usize
+            {   return Err ( format!( "Matrix row {y} has {} cells, expected {width}", cells.len() ) )
+            }
+            for ( x, hex ) in cells.into_iter().enumerate()
+            {   let error = || format!( "Invalid cell \"{hex}\" at ({x}, {y})" );
+                let flag: Flag = hex.parse().map_err( | _ | error() )?;
+                if flag.terrain() == Terrain::Undef { return Err ( error() ) }
+                *map.matrix_mut( IVec2::new( x as i32, y as i32 ) ) = flag;
+            }
+        }
+
+        //スタート地点とゴール(空地であること)
+        for ( name, ( x, y ) ) in [ ( "Start", file.start ), ( "Goal", file.goal ) ]
+        {   if ! map.is_space( IVec2::new( x, y ) )
+            {   return Err ( format!( "{name} ({x}, {y}) is not a space" ) )
+            }
+        }
+        map.start = IVec2::new( file.start.0, file.start.1 );
+        map.goal  = IVec2::new( file.goal.0 , file.goal.1  );
+
+        Ok ( map )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,164 @@
+use super::*;
+use serde::{ Deserialize, Deserializer, Serialize, Serializer, de::Error };
+
+////////////////////////////////////////////////////////////////////////////////
+
+//マスの地形(一つのマスに一つだけ)
+//＜注意＞ 値は迷路ファイルのビットそのもの。変えると古いファイルが読めなくなる
+#[derive( Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize )]
+pub enum Terrain
+{   #[default]
+    Undef = 0b00, //未定義
+    Space = 0b01, //空地
+    Wall  = 0b10, //壁
+}
+
+//地形のビットの範囲(0b11は将来の地形のために空けてある)
+const TERRAIN_MASK: u128 = 0b11;
+const TERRAIN_BITS: u32  = 2;
+
+impl Terrain
+{   //全ての地形
+    pub const ALL: [ Terrain; 3 ] = [ Terrain::Undef, Terrain::Space, Terrain::Wall ];
+
+    //ビットから地形を決める(どの地形でもなければNone)
+    fn from_bits( bits: u128 ) -> Option<Self>
+    {   Self::ALL.into_iter().find( | &terrain | terrain as u128 == bits & TERRAIN_MASK )
+    }
+}
+
+//地形に重ねるフラグ(一つのマスにいくつでも)
+//＜注意＞ 並び順がビットの位置になる。追加は末尾に、並べ替えはしないこと
+#[derive( Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize )]
+pub enum Overlay
+{   DeadEnd,  //行き止り(穴掘り法で後戻りしたマス)
+    Visited,  //通ったマス
+    Solution, //解の経路上のマス
+    Item,     //アイテムがあるマス
+    Door,     //扉があるマス
+}
+
+impl Overlay
+{   //全てのフラグ
+    pub const ALL: [ Overlay; 5 ] =
+    [   Overlay::DeadEnd, Overlay::Visited, Overlay::Solution, Overlay::Item, Overlay::Door,
+    ];
+
+    //フラグのビット(地形のビットの上に並べる。フラグは126個まで)
+    const fn bit( self ) -> u128 { 1 << ( TERRAIN_BITS + self as u32 ) }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//マスの情報(地形とフラグのビット)
+//知らないビットも消さずに残す(新しい版で保存したファイルを読んで保存し直しても失わない)
+#[derive( Clone, Copy, PartialEq, Eq, Default )]
+pub struct Flag ( u128 );
+
+impl Flag
+{   //地形を指定して作る(フラグはなし)
+    pub fn new( terrain: Terrain ) -> Self { Flag ( terrain as u128 ) }
+
+    //地形(ビットがどの地形でもなければUndef)
+    pub fn terrain( self ) -> Terrain
+    {   Terrain::from_bits( self.0 ).unwrap_or_default()
+    }
+
+    //地形だけを書き換える(フラグは残す)
+    pub fn set_terrain( &mut self, terrain: Terrain )
+    {   self.0 = ( self.0 & ! TERRAIN_MASK ) | terrain as u128;
+    }
+
+    //フラグを立てる・消す・調べる
+    pub fn set  ( &mut self, overlay: Overlay ) { self.0 |= overlay.bit() }
+    pub fn clear( &mut self, overlay: Overlay ) { self.0 &= ! overlay.bit() }
+    pub fn has  ( self, overlay: Overlay ) -> bool { self.0 & overlay.bit() != 0 }
+
+    //立っているフラグ
+    pub fn overlays( self ) -> impl Iterator<Item = Overlay>
+    {   Overlay::ALL.into_iter().filter( move | &overlay | self.has( overlay ) )
+    }
+
+    //地形もフラグも全てのビット
+    pub fn bits( self ) -> u128 { self.0 }
+}
+
+//迷路ファイルと同じ16進数の表記
+impl std::fmt::LowerHex for Flag
+{   fn fmt( &self, f: &mut std::fmt::Formatter ) -> std::fmt::Result
+    {   std::fmt::LowerHex::fmt( &self.0, f )
+    }
+}
+
+//16進数から作る(地形のビットはUndef、Space、Wallのどれかであること)
+impl std::str::FromStr for Flag
+{   type Err = String;
+    fn from_str( hex: &str ) -> Result<Self, Self::Err>
+    {   let bits = u128::from_str_radix( hex, 16 ).map_err( | err | format!( "\"{hex}\": {err}" ) )?;
+        if Terrain::from_bits( bits ).is_none()
+        {   return Err ( format!( "\"{hex}\": unknown terrain bits {:#b}", bits & TERRAIN_MASK ) )
+        }
+        Ok ( Flag ( bits ) )
+    }
+}
+
+//「地形 | フラグ | ...」の形で表示する(知らないビットは16進数で付ける)
+impl std::fmt::Debug for Flag
+{   fn fmt( &self, f: &mut std::fmt::Formatter ) -> std::fmt::Result
+    {   write!( f, "{:?}", self.terrain() )?;
+        for overlay in self.overlays() { write!( f, " | {overlay:?}" )? }
+
+        let known = Overlay::ALL.iter().fold( TERRAIN_MASK, | bits, overlay | bits | overlay.bit() );
+        let unknown = self.0 & ! known;
+        if unknown != 0 { write!( f, " | {unknown:#x}" )? }
+        Ok ( () )
+    }
+}
+
+//シリアライズは16進数の文字列にする
+impl Serialize for Flag
+{   fn serialize<S: Serializer>( &self, serializer: S ) -> Result<S::Ok, S::Error>
+    {   serializer.serialize_str( &format!( "{self:x}" ) )
+    }
+}
+
+impl<'de> Deserialize<'de> for Flag
+{   fn deserialize<D: Deserializer<'de>>( deserializer: D ) -> Result<Self, D::Error>
+    {   String::deserialize( deserializer )?.parse().map_err( D::Error::custom )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（マスの地形とフラグ）
+impl Map
+{   //指定の位置のマスの情報(範囲外はNone)
+    pub fn flag( &self, cell: IVec2 ) -> Option<Flag>
+    {   self.is_inside( cell ).then( || *self.matrix( cell ) )
+    }
+
+    //指定の位置の地形(範囲外は壁にする)
+    pub fn terrain( &self, cell: IVec2 ) -> Terrain
+    {   self.flag( cell ).map_or( Terrain::Wall, Flag::terrain )
+    }
+
+    //指定の位置のフラグを立てる・消す・調べる(範囲外は何もしない、フラグもない)
+    pub fn set_overlay( &mut self, cell: IVec2, overlay: Overlay )
+    {   if self.is_inside( cell ) { self.matrix_mut( cell ).set( overlay ) }
+    }
+    pub fn clear_overlay( &mut self, cell: IVec2, overlay: Overlay )
+    {   if self.is_inside( cell ) { self.matrix_mut( cell ).clear( overlay ) }
+    }
+    pub fn has_overlay( &self, cell: IVec2, overlay: Overlay ) -> bool
+    {   self.flag( cell ).is_some_and( | flag | flag.has( overlay ) )
+    }
+
+    //全てのマスのフラグを消す(地形は残す)
+    pub fn clear_overlay_all( &mut self, overlay: Overlay )
+    {   self.matrix.iter_mut().flatten().for_each( | flag | flag.clear( overlay ) );
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,127 @@
+use super::*;
+
+//アルゴリズム別の実装
+mod prim;
+mod kruskal;
+mod wilson;
+mod eller;
+mod division;
+mod growing_tree;
+mod dungeon;
+pub use dungeon::Dungeon;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路作成アルゴリズムのtrait
+//＜前提＞ start()は、Mapが壁で埋められスタート地点だけが空地になった状態で呼ばれる
+pub trait MazeGenerator: Sync
+{   fn name( &self ) -> &'static str; //フッター等に表示する名前
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>; //迷路を一歩ずつ作る状態を用意する
+
+    //ループも2x2の広場もない迷路(木)を作るか？(ループ化の前)
+    fn is_perfect( &self ) -> bool { true }
+}
+
+//迷路を一歩ずつ作る途中の状態
+//どの一歩の後で止めても、次のstep()で続きから再開できる
+pub trait GeneratorState: Send + Sync
+{   fn step( &mut self, map: &mut Map ) -> bool; //Mapのmatrixに一歩書き込む(作り終えていたら何もせずfalse)
+}
+
+//実行時に選択できるアルゴリズムの一覧
+counted_array!
+(   pub const GENERATORS: [ &dyn MazeGenerator; _ ] =
+    [   &DepthFirst,
+        &prim::Prim,
+        &kruskal::Kruskal,
+        &wilson::Wilson,
+        &eller::Eller,
+        &division::RecursiveDivision,
+        &growing_tree::GrowingTree { newest_ratio: 0.5 },
+        &dungeon::Dungeon,
+    ]
+);
+
+//名前からアルゴリズムを探す(大文字小文字は区別しない)
+pub fn find_generator( name: &str ) -> Option<usize>
+{   GENERATORS.iter().position( | generator | generator.name().eq_ignore_ascii_case( name ) )
+}
+
+//アルゴリズムの名前の一覧
+pub fn generator_names() -> Vec<&'static str>
+{   GENERATORS.iter().map( | generator | generator.name() ).collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//穴掘り法(ランダムな深さ優先探索)
+//格子を使わず、壁のマスを一つずつ掘り進める
+pub struct DepthFirst;
+
+impl MazeGenerator for DepthFirst
+{   fn name( &self ) -> &'static str { "DEPTH-FIRST" }
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState> { Box::new( DepthFirstState { cell: Some ( map.start ) } ) }
+}
+
+//掘っている位置(掘り終えたらNone)
+struct DepthFirstState { cell: Option<IVec2> }
+
+impl GeneratorState for DepthFirstState
+{   fn step( &mut self, map: &mut Map ) -> bool
+    {   let Some ( cell ) = self.cell else { return false };
+        self.cell = map.dig_labyrinth( cell );
+        true
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//格子を使うアルゴリズム用のメソッド
+//スタート地点と偶奇が同じ内側のマスを「部屋」とし、部屋の間のマスを「通路」とする
+impl Map
+{   //部屋のマスか？
+    fn is_lattice( &self, cell: IVec2 ) -> bool
+    {   self.is_inside_inner( cell )
+        && ( cell.x - self.start.x ) % 2 == 0
+        && ( cell.y - self.start.y ) % 2 == 0
+    }
+
+    //全ての部屋のマス(x優先で左上から)
+    fn lattice_cells( &self ) -> Vec<IVec2>
+    {   let mut cells = Vec::new();
+        for x in self.x_range_inner()
+        {   for y in self.y_range_inner()
+            {   let cell = IVec2::new( x, y );
+                if self.is_lattice( cell ) { cells.push( cell ) }
+            }
+        }
+        cells
+    }
+
+    //四方の隣の部屋（方向と座標）
+    fn lattice_neighbors( &self, cell: IVec2 ) -> Vec<( News, IVec2 )>
+    {   NEWS.iter()
+        .map( | &news | ( news, cell + news + news ) )
+        .filter( | &( _, next ) | self.is_lattice( next ) )
+        .collect()
+    }
+
+    //部屋から隣の部屋へ通路を掘る
+    fn carve_passage( &mut self, cell: IVec2, news: News )
+    {   self.set_space( cell );
+        self.set_space( cell + news );
+        self.set_space( cell + news + news );
+    }
+
+    //matrixと同じ並びの一次元配列を作るための添字
+    fn lattice_index( &self, IVec2 { x, y }: IVec2 ) -> usize
+    {   ( x * self.height + y ) as usize
+    }
+    fn lattice_len( &self ) -> usize
+    {   ( self.width * self.height ) as usize
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,74 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//再帰分割法
+//部屋の範囲を全て空地にしてから、一か所だけ穴の空いた壁で再帰的に分割する
+//（一歩ずつ作れるよう、再帰の代わりに分割する区画をスタックに積む）
+pub struct RecursiveDivision;
+
+impl MazeGenerator for RecursiveDivision
+{   fn name( &self ) -> &'static str { "DIVISION" }
+
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>
+    {   //部屋の範囲(左上と右下の部屋)
+        let cells = map.lattice_cells();
+        let ( Some ( &min ), Some ( &max ) ) = ( cells.first(), cells.last() ) else
+        {   return Box::new( DivisionState { stack: Vec::new() } )
+        };
+
+        //範囲全体を空地にする
+        for x in min.x..=max.x
+        {   for y in min.y..=max.y
+            {   map.set_space( IVec2::new( x, y ) );
+            }
+        }
+
+        Box::new( DivisionState { stack: vec![ ( min, max ) ] } )
+    }
+}
+
+//これから分割する区画(左上と右下の部屋。末尾から取り出す)
+struct DivisionState { stack: Vec<( IVec2, IVec2 )> }
+
+impl GeneratorState for DivisionState
+{   //区画を一つ分割する(分割する区画がなくなったら完成)
+    fn step( &mut self, map: &mut Map ) -> bool
+    {   let Some ( ( min, max ) ) = self.stack.pop() else { return false };
+
+        //部屋の数
+        let w = ( max.x - min.x ) / 2 + 1;
+        let h = ( max.y - min.y ) / 2 + 1;
+        if w < 2 || h < 2 { return true } //これ以上分割できない
+
+        //長い辺を分割する(同じならランダム)
+        let is_horizontal = if w == h { map.random_bool( 0.5 ) } else { h > w };
+
+        //二つに分けた区画は、左上の方から先に分割する
+        let halves = if is_horizontal
+        {   //横向きの壁を作り、部屋一つ分の穴を空ける
+            let wall_y = min.y + map.random_index( h as usize - 1 ) as i32 * 2 + 1;
+            let hole_x = min.x + map.random_index( w as usize ) as i32 * 2;
+            for x in min.x..=max.x
+            {   if x != hole_x { map.set_wall( IVec2::new( x, wall_y ) ) }
+            }
+            [ ( min, IVec2::new( max.x, wall_y - 1 ) ), ( IVec2::new( min.x, wall_y + 1 ), max ) ]
+        }
+        else
+        {   //縦向きの壁を作り、部屋一つ分の穴を空ける
+            let wall_x = min.x + map.random_index( w as usize - 1 ) as i32 * 2 + 1;
+            let hole_y = min.y + map.random_index( h as usize ) as i32 * 2;
+            for y in min.y..=max.y
+            {   if y != hole_y { map.set_wall( IVec2::new( wall_x, y ) ) }
+            }
+            [ ( min, IVec2::new( wall_x - 1, max.y ) ), ( IVec2::new( wall_x + 1, min.y ), max ) ]
+        };
+        self.stack.extend( halves.into_iter().rev() );
+
+        true
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
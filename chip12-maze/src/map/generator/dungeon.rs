@@ -0,0 +1,154 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//部屋と通路のダンジョン
+//重ならない長方形の部屋を置き、各部屋から先に置いた部屋へL字の通路を掘ってつなぐ
+//部屋の角と通路は格子の部屋のマスに揃えるので、部屋どうしの間には必ず壁が残る
+//（部屋は広場、通路が交差すればループになる。迷路のような木にはならない）
+//部屋の数と縦横幅の範囲はMapのroom_count()とroom_size()に従う
+pub struct Dungeon;
+
+//部屋(左上と右下のマス)
+#[derive( Clone, Copy )]
+struct Room { min: IVec2, max: IVec2 }
+
+impl Room
+{   //他の部屋と重なるか？
+    fn overlaps( &self, other: &Room ) -> bool
+    {   self.min.x <= other.max.x && other.min.x <= self.max.x &&
+        self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+}
+
+impl MazeGenerator for Dungeon
+{   fn name( &self ) -> &'static str { "DUNGEON" }
+    fn is_perfect( &self ) -> bool { false }
+
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>
+    {   //スタート地点を含む最初の部屋(置けなければスタート地点だけ)
+        let start = map.start;
+        let first = self.place_room
+        (   map, &[],
+            | map, steps |
+            {   //スタート地点が部屋の中のどこに来るかもランダムにする
+                let offset = IVec2::new( map.random_in( 0..=steps.x ), map.random_in( 0..=steps.y ) );
+                start - offset * 2
+            }
+        );
+        let mut rooms = vec![ first.unwrap_or( Room::new( start, IVec2::ZERO ) ) ];
+
+        //残りの部屋を重ならないように置く(格子の部屋のマスより多くは置けない)
+        let lattice = map.lattice_cells();
+        let count = map.random_in( map.room_count.clone() ).min( lattice.len() as i32 );
+        for _ in 1..count
+        {   let room = self.place_room( map, &rooms, | map, _ | lattice[ map.random_index( lattice.len() ) ] );
+            if let Some ( room ) = room { rooms.push( room ) }
+        }
+
+        Box::new( DungeonState { rooms, dug: 0, connected: 1 } )
+    }
+}
+
+//置いた部屋と、掘った部屋の数、通路でつないだ部屋の数
+struct DungeonState
+{   rooms    : Vec<Room>,
+    dug      : usize,
+    connected: usize,
+}
+
+impl GeneratorState for DungeonState
+{   //部屋を一つ掘る。全て掘ったら、各部屋から先に置いた部屋のどれかへ通路を一本ずつ掘る
+    //(全ての部屋がつながったら完成)
+    fn step( &mut self, map: &mut Map ) -> bool
+    {   if let Some ( room ) = self.rooms.get( self.dug )
+        {   for x in room.min.x..=room.max.x
+            {   for y in room.min.y..=room.max.y
+                {   map.set_space( IVec2::new( x, y ) );
+                }
+            }
+            self.dug += 1;
+            return true
+        }
+
+        let i = self.connected;
+        if i >= self.rooms.len() { return false }
+        let j = map.random_index( i );
+        let from = map.random_lattice_in( &self.rooms[ i ] );
+        let to   = map.random_lattice_in( &self.rooms[ j ] );
+        let is_horizontal_first = map.random_bool( 0.5 );
+        map.dig_corridor( from, to, is_horizontal_first );
+        self.connected += 1;
+
+        true
+    }
+}
+
+impl Room
+{   //左上のマスと、右下までの格子の歩数(縦横幅 = 2 * 歩数 + 1)から部屋を作る
+    fn new( min: IVec2, steps: IVec2 ) -> Self { Room { min, max: min + steps * 2 } }
+}
+
+impl Dungeon
+{   //ランダムな大きさの部屋を、外壁の内側で他の部屋と重ならない場所に置く(置けなければNone)
+    //大きさは格子の歩数で決める(縦横幅は奇数に切り上げる)。左上のマスはposition()で決める
+    fn place_room<F>( &self, map: &mut Map, rooms: &[ Room ], mut position: F ) -> Option<Room>
+    where F: FnMut( &mut Map, IVec2 ) -> IVec2
+    {   for _ in 0..DUNGEON_ROOM_MAX_RETRY
+        {   let steps = | map: &mut Map | map.random_in( map.room_size.clone() ).max( 1 ) / 2;
+            let steps = IVec2::new( steps( map ), steps( map ) );
+            let room = Room::new( position( map, steps ), steps );
+
+            if map.is_room_inside( &room ) && ! rooms.iter().any( | other | room.overlaps( other ) )
+            {   return Some ( room )
+            }
+        }
+
+        None
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//ダンジョン用のメソッド
+impl Map
+{   //部屋の角が格子の部屋のマスか？(外壁の内側に収まる)
+    fn is_room_inside( &self, room: &Room ) -> bool
+    {   self.is_lattice( room.min ) && self.is_lattice( room.max )
+    }
+
+    //部屋の中のランダムな格子の部屋のマス
+    fn random_lattice_in( &mut self, room: &Room ) -> IVec2
+    {   let steps = ( room.max - room.min ) / 2;
+        let x = self.random_in( 0..=steps.x );
+        let y = self.random_in( 0..=steps.y );
+        room.min + IVec2::new( x, y ) * 2
+    }
+
+    //範囲内の一様乱数
+    fn random_in( &mut self, range: RangeInclusive<i32> ) -> i32
+    {   let ( lo, hi ) = range.into_inner();
+        if hi <= lo { return lo }
+        lo + self.random_index( ( hi - lo + 1 ) as usize ) as i32
+    }
+
+    //L字の通路を掘る(横から先か縦から先か)
+    fn dig_corridor( &mut self, from: IVec2, to: IVec2, is_horizontal_first: bool )
+    {   let mut cell = from;
+        let ( east_west, north_south ) = ( to.x - from.x, to.y - from.y );
+        let horizontal = ( if east_west > 0 { News::East } else { News::West }, east_west.unsigned_abs() );
+        let vertical   = ( if north_south > 0 { News::South } else { News::North }, north_south.unsigned_abs() );
+        let legs = if is_horizontal_first { [ horizontal, vertical ] } else { [ vertical, horizontal ] };
+
+        for ( news, steps ) in legs
+        {   for _ in 0..steps
+            {   cell = cell + news;
+                self.set_space( cell );
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,93 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//エラー法
+//部屋を一行ずつ処理し、行内の集合の併合と下の行への延長を繰り返す
+pub struct Eller;
+
+impl MazeGenerator for Eller
+{   fn name( &self ) -> &'static str { "ELLER" }
+
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>
+    {   //部屋を行ごとに分ける(y座標の昇順、行内はx座標の昇順)
+        let mut rows: Vec<Vec<IVec2>> = Vec::new();
+        let mut cells = map.lattice_cells();
+        cells.sort_by_key( | cell | ( cell.y, cell.x ) );
+        for cell in cells
+        {   match rows.last_mut()
+            {   Some ( row ) if row[ 0 ].y == cell.y => row.push( cell ),
+                _ => rows.push( vec![ cell ] ),
+            }
+        }
+
+        //部屋ごとの集合番号(0は未割当て)
+        let set_id = vec![ 0; map.lattice_len() ];
+
+        Box::new( EllerState { rows, next_row: 0, set_id, next_id: 1 } )
+    }
+}
+
+//部屋の行と次に処理する行、部屋ごとの集合番号と次に割り当てる番号
+struct EllerState
+{   rows    : Vec<Vec<IVec2>>,
+    next_row: usize,
+    set_id  : Vec<usize>,
+    next_id : usize,
+}
+
+impl GeneratorState for EllerState
+{   //一行処理する(全ての行を処理したら完成)
+    fn step( &mut self, map: &mut Map ) -> bool
+    {   let i = self.next_row;
+        let Some ( row ) = self.rows.get( i ) else { return false };
+        let is_last = i + 1 == self.rows.len();
+        let set_id = &mut self.set_id;
+        self.next_row += 1;
+
+        //集合に属していない部屋へ新しい集合番号を割り当てる
+        for &cell in row
+        {   let id = &mut set_id[ map.lattice_index( cell ) ];
+            if *id == 0 { *id = self.next_id; self.next_id += 1; }
+        }
+
+        //左右の部屋が別の集合ならランダムに併合する(最終行は必ず併合する)
+        for pair in row.windows( 2 )
+        {   let a = set_id[ map.lattice_index( pair[ 0 ] ) ];
+            let b = set_id[ map.lattice_index( pair[ 1 ] ) ];
+            if a == b || ( ! is_last && map.random_bool( 0.5 ) ) { continue }
+
+            map.carve_passage( pair[ 0 ], News::East );
+            for &cell in row
+            {   let id = &mut set_id[ map.lattice_index( cell ) ];
+                if *id == b { *id = a }
+            }
+        }
+        if is_last { return true }
+
+        //集合ごとに少なくとも一つ、下の行へ通路を延ばす
+        let mut sets: Vec<_> = row.iter().map( | &cell | set_id[ map.lattice_index( cell ) ] ).collect();
+        sets.sort_unstable();
+        sets.dedup();
+        for id in sets
+        {   let mut members: Vec<_> = row.iter()
+                .copied()
+                .filter( | &cell | set_id[ map.lattice_index( cell ) ] == id )
+                .collect();
+            map.shuffle( &mut members );
+
+            let count = map.random_index( members.len() ) + 1;
+            for &cell in &members[ ..count ]
+            {   map.carve_passage( cell, News::South );
+                let below = map.lattice_index( cell + News::South + News::South );
+                set_id[ below ] = id;
+            }
+        }
+
+        true
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,63 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//成長木法
+//作業中の部屋リストから部屋を選んで掘り進める。選び方で性質が変わる
+//（最新の部屋だけなら穴掘り法、ランダムだけならプリム法に近くなる）
+pub struct GrowingTree
+{   pub newest_ratio: f64, //最新の部屋を選ぶ確率(残りはランダムに選ぶ)
+}
+
+impl MazeGenerator for GrowingTree
+{   fn name( &self ) -> &'static str { "GROWING-TREE" }
+
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>
+    {   Box::new( GrowingTreeState { newest_ratio: self.newest_ratio, active: vec![ map.start ] } )
+    }
+}
+
+//最新の部屋を選ぶ確率と、作業中の部屋
+struct GrowingTreeState
+{   newest_ratio: f64,
+    active      : Vec<IVec2>,
+}
+
+impl GeneratorState for GrowingTreeState
+{   //作業中の部屋を一つ選んで掘り進める(作業中の部屋がなくなったら完成)
+    fn step( &mut self, map: &mut Map ) -> bool
+    {   if self.active.is_empty() { return false }
+
+        //作業中の部屋を選ぶ
+        let i = if map.random_bool( self.newest_ratio )
+        {   self.active.len() - 1
+        }
+        else
+        {   map.random_index( self.active.len() )
+        };
+        let cell = self.active[ i ];
+
+        //未到達の隣の部屋
+        let digable: Vec<_> = map.lattice_neighbors( cell )
+            .into_iter()
+            .filter( | &( _, next ) | map.is_wall( next ) )
+            .collect();
+
+        if digable.is_empty()
+        {   //掘れる部屋がないなら作業中から外す
+            self.active.remove( i );
+        }
+        else
+        {   //ランダムな方向へ掘り進める
+            let ( news, next ) = digable[ map.random_index( digable.len() ) ];
+            map.carve_passage( cell, news );
+            self.active.push( next );
+        }
+
+        true
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,62 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//クラスカル法(ランダム)
+//全ての通路をシャッフルし、別々の集合に属する部屋をつなぐ通路だけ掘る
+pub struct Kruskal;
+
+impl MazeGenerator for Kruskal
+{   fn name( &self ) -> &'static str { "KRUSKAL" }
+
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>
+    {   //部屋の集合(Union-Find)を準備する
+        let parent = ( 0..map.lattice_len() ).collect();
+
+        //東と南への通路をすべて並べてシャッフルする
+        let mut passages = Vec::new();
+        for cell in map.lattice_cells()
+        {   for ( news, _ ) in map.lattice_neighbors( cell )
+            {   if matches!( news, News::East | News::South ) { passages.push( ( cell, news ) ) }
+            }
+        }
+        map.shuffle( &mut passages );
+        passages.reverse(); //末尾から取り出すので
+
+        Box::new( KruskalState { parent, passages } )
+    }
+}
+
+//部屋の集合と、まだ調べていない通路
+struct KruskalState
+{   parent  : Vec<usize>,
+    passages: Vec<( IVec2, News )>,
+}
+
+impl GeneratorState for KruskalState
+{   //両側の部屋が別の集合なら通路を掘って集合を併合する(通路を調べ終えたら完成)
+    fn step( &mut self, map: &mut Map ) -> bool
+    {   let Some ( ( cell, news ) ) = self.passages.pop() else { return false };
+        let a = find_root( &mut self.parent, map.lattice_index( cell ) );
+        let b = find_root( &mut self.parent, map.lattice_index( cell + news + news ) );
+        if a == b { return true }
+
+        self.parent[ b ] = a;
+        map.carve_passage( cell, news );
+
+        true
+    }
+}
+
+//集合の代表を探す(経路圧縮あり)
+fn find_root( parent: &mut [ usize ], mut i: usize ) -> usize
+{   while parent[ i ] != i
+    {   parent[ i ] = parent[ parent[ i ] ];
+        i = parent[ i ];
+    }
+    i
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,49 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//プリム法(ランダム)
+//迷路に接する通路の候補からランダムに一つ選んで掘ることを繰り返す
+pub struct Prim;
+
+impl MazeGenerator for Prim
+{   fn name( &self ) -> &'static str { "PRIM" }
+
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>
+    {   //スタート地点の四方を候補にする
+        let start = map.start;
+        let frontier = map.lattice_neighbors( start )
+            .into_iter()
+            .map( | ( news, _ ) | ( start, news ) )
+            .collect();
+
+        Box::new( PrimState { frontier } )
+    }
+}
+
+//迷路に接する通路の候補(部屋と方向)
+struct PrimState { frontier: Vec<( IVec2, News )> }
+
+impl GeneratorState for PrimState
+{   //候補を一つ選んで掘る(候補がなくなったら完成)
+    fn step( &mut self, map: &mut Map ) -> bool
+    {   if self.frontier.is_empty() { return false }
+        let ( cell, news ) = self.frontier.swap_remove( map.random_index( self.frontier.len() ) );
+        let next = cell + news + news;
+
+        //既に迷路に含まれる部屋なら掘らない(ループを作らない)
+        if map.is_space( next ) { return true }
+        map.carve_passage( cell, news );
+
+        //新しい部屋から未到達の部屋への通路を候補に加える
+        for ( news, side ) in map.lattice_neighbors( next )
+        {   if map.is_wall( side ) { self.frontier.push( ( next, news ) ) }
+        }
+
+        true
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
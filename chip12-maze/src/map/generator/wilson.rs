@@ -0,0 +1,68 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//ウィルソン法(ループ除去ランダムウォーク)
+//未到達の部屋からランダムウォークで迷路に着くまで歩き、ループを消した道を掘る
+pub struct Wilson;
+
+impl MazeGenerator for Wilson
+{   fn name( &self ) -> &'static str { "WILSON" }
+
+    fn start( &self, map: &mut Map ) -> Box<dyn GeneratorState>
+    {   //未到達の部屋(スタート地点は最初から迷路に含まれる)
+        let start = map.start;
+        let mut unvisited: Vec<_> = map.lattice_cells()
+            .into_iter()
+            .filter( | &cell | cell != start )
+            .collect();
+        map.shuffle( &mut unvisited );
+
+        //ランダムウォークで最後に出た方向を部屋ごとに記録する
+        let last_news = vec![ News::default(); map.lattice_len() ];
+
+        Box::new( WilsonState { unvisited, last_news } )
+    }
+}
+
+//未到達の部屋(末尾から取り出す)と、部屋ごとの最後に出た方向
+struct WilsonState
+{   unvisited: Vec<IVec2>,
+    last_news: Vec<News>,
+}
+
+impl GeneratorState for WilsonState
+{   //未到達の部屋から一本の道を掘る(未到達の部屋がなくなったら完成)
+    fn step( &mut self, map: &mut Map ) -> bool
+    {   let Some ( origin ) = self.unvisited.pop() else { return false };
+        if map.is_space( origin ) { return true } //既に迷路に含まれる
+
+        //迷路に当たるまで歩く(同じ部屋を再訪したら方向を上書きしてループを消す)
+        let mut cell = origin;
+        while ! map.is_space( cell )
+        {   let neighbors = map.lattice_neighbors( cell );
+            let ( news, next ) = neighbors[ map.random_index( neighbors.len() ) ];
+            let i = map.lattice_index( cell );
+            self.last_news[ i ] = news;
+            cell = next;
+        }
+
+        //記録した方向をたどって道を決める
+        let mut path = Vec::new();
+        let mut cell = origin;
+        while ! map.is_space( cell )
+        {   let news = self.last_news[ map.lattice_index( cell ) ];
+            path.push( ( cell, news ) );
+            cell = cell + news + news;
+        }
+
+        //道を掘る
+        path.into_iter().for_each( | ( cell, news ) | map.carve_passage( cell, news ) );
+
+        true
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
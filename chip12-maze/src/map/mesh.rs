@@ -0,0 +1,162 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//壁と床のメッシュの設定
+//壁は隣の壁とすき間なくつなげ、壁どうしが接する面(外から見えない面)は作らない
+pub const WALL_CHUNK_GRIDS : i32 = 16;    //一つのメッシュにまとめる範囲(縦横Grid)
+pub const WALL_MESH_TOP    : f32 = 0.45;  //壁の上面の高さ
+pub const WALL_MESH_BOTTOM : f32 = -0.5;  //壁の底の高さ(地面)
+pub const GOAL_MESH_SIZE   : f32 = 0.4;   //ゴールの目印の縦横高さ
+
+//メッシュの頂点データ(3D表示とファイルへのエクスポートで共用する。bevyのMeshへの変換は使う側で行う)
+//面ごとに頂点を持ち、UVは面ごとに0.0～1.0を割り当てる(テクスチャを一枚ずつ貼る)
+#[derive( Default )]
+pub struct MeshData
+{   pub positions: Vec<[ f32; 3 ]>,
+    pub normals  : Vec<[ f32; 3 ]>,
+    pub uvs      : Vec<[ f32; 2 ]>,
+    pub indices  : Vec<u32>,
+}
+
+impl MeshData
+{   //面を一つ追加する(中心、法線、横方向と縦方向の半分の長さのベクトル)
+    fn add_face( &mut self, center: Vec3, normal: Vec3, u: Vec3, v: Vec3 )
+    {   let base = self.positions.len() as u32;
+        let corners = [ -u - v, u - v, u + v, -u + v ];
+        let uv = [ [ 0.0, 1.0 ], [ 1.0, 1.0 ], [ 1.0, 0.0 ], [ 0.0, 0.0 ] ];
+        for i in 0..4
+        {   self.positions.push( ( center + corners[ i ] ).to_array() );
+            self.normals.push( normal.to_array() );
+            self.uvs.push( uv[ i ] );
+        }
+        self.indices.extend( [ 0, 1, 2, 0, 2, 3 ].map( | i | base + i ) );
+    }
+
+    //上面を追加する(中心と縦横幅)
+    fn add_top( &mut self, center: Vec3, size: f32 )
+    {   self.add_face( center, Vec3::Y, Vec3::X * size / 2.0, Vec3::NEG_Z * size / 2.0 );
+    }
+
+    //側面を追加する(箱の中心、四方、縦横幅、高さ)
+    fn add_side( &mut self, center: Vec3, news: News, size: f32, height: f32 )
+    {   let normal = to_3dxz( IVec2::ZERO + news );
+        self.add_face
+        (   center + normal * size / 2.0,
+            normal,
+            Vec3::Y.cross( normal ) * size / 2.0,
+            Vec3::Y * height / 2.0,
+        );
+    }
+
+    //地面に置いた箱を追加する(底面は作らない)
+    fn add_box( &mut self, center: Vec3, size: f32, height: f32 )
+    {   self.add_top( center + Vec3::Y * height / 2.0, size );
+        NEWS.iter().for_each( | &news | self.add_side( center, news, size, height ) );
+    }
+
+    //面がないか？
+    pub fn is_empty( &self ) -> bool { self.indices.is_empty() }
+
+    //別の頂点データを後ろにつなげる
+    pub fn append( &mut self, other: MeshData )
+    {   let base = self.positions.len() as u32;
+        self.positions.extend( other.positions );
+        self.normals  .extend( other.normals   );
+        self.uvs      .extend( other.uvs       );
+        self.indices  .extend( other.indices.into_iter().map( | i | base + i ) );
+    }
+}
+
+//平面座標(IVec2)から3D直交座標(Vec3)へ変換する(XZ平面上)
+fn to_3dxz( cell: IVec2 ) -> Vec3
+{   Vec3::new( cell.x as f32, 0.0, cell.y as f32 )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Map
+{   //チャンクの範囲(縦横のチャンク数)
+    pub fn wall_chunks( &self ) -> IVec2
+    {   let chunks = | grids: i32 | ( grids + WALL_CHUNK_GRIDS - 1 ) / WALL_CHUNK_GRIDS;
+        IVec2::new( chunks( self.width ), chunks( self.height ) )
+    }
+
+    //全てのチャンク(x優先で左上から)
+    pub fn wall_chunk_iter( &self ) -> impl Iterator<Item = IVec2>
+    {   let chunks = self.wall_chunks();
+        ( 0..chunks.x ).flat_map( move | x | ( 0..chunks.y ).map( move | y | IVec2::new( x, y ) ) )
+    }
+
+    //チャンクに含まれるマス
+    fn chunk_cells( &self, chunk: IVec2 ) -> impl Iterator<Item = IVec2>
+    {   let zero = chunk * WALL_CHUNK_GRIDS;
+        let x_range = zero.x..( zero.x + WALL_CHUNK_GRIDS ).min( self.width  );
+        let y_range = zero.y..( zero.y + WALL_CHUNK_GRIDS ).min( self.height );
+        x_range.flat_map( move | x | y_range.clone().map( move | y | IVec2::new( x, y ) ) )
+    }
+
+    //壁のマスの四方のうち、面を作る方向か？(隣が壁でない。マップの外側にも面を作る)
+    pub fn is_wall_face( &self, cell: IVec2, news: News ) -> bool
+    {   let next = cell + news;
+        ! self.is_inside( next ) || ! self.is_wall( next )
+    }
+
+    //チャンク一つ分の壁の頂点データ
+    //＜注意＞ 原点の壁は親Entityが描くので含めない
+    pub fn wall_chunk_data( &self, chunk: IVec2 ) -> MeshData
+    {   let mut data = MeshData::default();
+        let height = WALL_MESH_TOP - WALL_MESH_BOTTOM;
+        let mid_y  = ( WALL_MESH_TOP + WALL_MESH_BOTTOM ) / 2.0;
+
+        for cell in self.chunk_cells( chunk )
+        {   if cell == IVec2::ZERO || ! self.is_wall( cell ) { continue }
+
+            //上面と側面(隣が壁の方向は作らない)
+            let center = to_3dxz( cell ) + Vec3::Y * mid_y;
+            data.add_top( to_3dxz( cell ) + Vec3::Y * WALL_MESH_TOP, 1.0 );
+            for news in NEWS
+            {   if self.is_wall_face( cell, news ) { data.add_side( center, news, 1.0, height ) }
+            }
+        }
+
+        data
+    }
+
+    //チャンク一つ分の床の頂点データ(床は壁のないマスにだけ一枚ずつ貼る)
+    pub fn floor_chunk_data( &self, chunk: IVec2 ) -> MeshData
+    {   let mut data = MeshData::default();
+
+        for cell in self.chunk_cells( chunk )
+        {   if ! self.is_wall( cell )
+            {   data.add_top( to_3dxz( cell ) + Vec3::Y * WALL_MESH_BOTTOM, 1.0 );
+            }
+        }
+
+        data
+    }
+
+    //原点の壁の頂点データ(隣の壁と接していても全ての側面を作る)
+    pub fn wall_zero_data( &self ) -> MeshData
+    {   let mut data = MeshData::default();
+        let height = WALL_MESH_TOP - WALL_MESH_BOTTOM;
+        let center = Vec3::Y * ( WALL_MESH_TOP + WALL_MESH_BOTTOM ) / 2.0;
+        data.add_box( center, 1.0, height );
+
+        data
+    }
+
+    //ゴールの目印の頂点データ(地面に置く)
+    pub fn goal_data( &self ) -> MeshData
+    {   let mut data = MeshData::default();
+        let center = to_3dxz( self.goal ) + Vec3::Y * ( WALL_MESH_BOTTOM + GOAL_MESH_SIZE / 2.0 );
+        data.add_box( center, GOAL_MESH_SIZE, GOAL_MESH_SIZE );
+
+        data
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,187 @@
+use super::*;
+use std::fmt::Write;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//GPUを使わずに迷路を真上から見た画像(PNG/SVG)にする
+//色はPaletteに従い、解の経路と行き止りの印を重ねて描ける
+#[derive( Clone, Copy )]
+pub struct RenderOptions
+{   pub cell_pixels: u32,  //1マスの縦横幅(Pixel)
+    pub solution   : bool, //解の経路を描くか？
+    pub dead_ends  : bool, //行き止りの印を描くか？
+}
+
+impl Default for RenderOptions
+{   fn default() -> Self
+    {   Self { cell_pixels: 8, solution: false, dead_ends: false }
+    }
+}
+
+//画像の設定(壁・床・ゴールの色はPaletteに従う)
+pub const RENDER_START_COLOR   : [ u8; 4 ] = [  50, 205,  50, 255 ]; //スタート地点(LimeGreen)
+pub const RENDER_SOLUTION_COLOR: [ u8; 4 ] = [   0, 255, 255, 255 ]; //解の経路(Cyan)
+pub const RENDER_DEAD_END_COLOR: [ u8; 4 ] = [ 255,  69,   0, 255 ]; //行き止りの印(OrangeRed)
+
+//重ねて描く図形(マスの中心を0.0とした、1マスあたりの比率)
+const RENDER_SOLUTION_WIDTH: f32 = 0.4; //経路の線の太さ
+const RENDER_DEAD_END_SIZE : f32 = 0.4; //行き止りの印の縦横幅
+
+//画像に描くもの(マスの色、解の経路、行き止りのマス)
+type RenderLayers = ( Vec<( IVec2, [ u8; 4 ] )>, Vec<IVec2>, Vec<IVec2> );
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Map
+{   //画像に描くもの(マスの色、解の経路、行き止りのマス)
+    fn render_layers( &self, palette: &Palette, options: RenderOptions ) -> RenderLayers
+    {   let mut cells = Vec::new();
+        for y in self.y_range()
+        {   for x in self.x_range()
+            {   let cell = IVec2::new( x, y );
+                let color =
+                    if      cell == self.start { RENDER_START_COLOR }
+                    else if cell == self.goal  { palette.goal       }
+                    else if self.is_wall( cell ) { palette.wall     }
+                    else                       { palette.floor      };
+                cells.push( ( cell, color ) );
+            }
+        }
+
+        let solution = if options.solution
+        {   self.path_from_start( self.goal ).unwrap_or_default()
+        }
+        else
+        {   Vec::new()
+        };
+
+        let dead_ends = if options.dead_ends
+        {   cells.iter()
+            .map( | &( cell, _ ) | cell )
+            .filter( | &cell | self.is_dead_end_shape( cell ) && cell != self.start && cell != self.goal )
+            .collect()
+        }
+        else
+        {   Vec::new()
+        };
+
+        ( cells, solution, dead_ends )
+    }
+
+    //RGBAの画素にする(戻り値は縦横幅と画素)
+    pub fn render_rgba( &self, palette: &Palette, options: RenderOptions ) -> ( u32, u32, Vec<u8> )
+    {   let px = options.cell_pixels.max( 1 );
+        let width  = self.width  as u32 * px;
+        let height = self.height as u32 * px;
+        let mut pixels = vec![ 0; ( width * height * 4 ) as usize ];
+
+        //矩形を塗る(マス単位の座標、範囲外は切り捨てる)
+        let mut fill = | min: Vec2, max: Vec2, rgba: [ u8; 4 ] |
+        {   let x_range = ( min.x * px as f32 ).round() as u32..( max.x * px as f32 ).round().min( width as f32 ) as u32;
+            let y_range = ( min.y * px as f32 ).round() as u32..( max.y * px as f32 ).round().min( height as f32 ) as u32;
+            for y in y_range
+            {   for x in x_range.clone()
+                {   let i = ( ( y * width + x ) * 4 ) as usize;
+                    pixels[ i..i + 4 ].copy_from_slice( &rgba );
+                }
+            }
+        };
+
+        let ( cells, solution, dead_ends ) = self.render_layers( palette, options );
+        for ( cell, color ) in cells
+        {   fill( cell.as_vec2(), cell.as_vec2() + Vec2::ONE, color );
+        }
+
+        //行き止りの印(マスの中心の正方形)
+        let half = Vec2::splat( RENDER_DEAD_END_SIZE / 2.0 );
+        for cell in dead_ends
+        {   let center = cell.as_vec2() + Vec2::splat( 0.5 );
+            fill( center - half, center + half, RENDER_DEAD_END_COLOR );
+        }
+
+        //解の経路(隣り合うマスの中心どうしを太い線でつなぐ)
+        let half = Vec2::splat( RENDER_SOLUTION_WIDTH / 2.0 );
+        for pair in solution.windows( 2 )
+        {   let a = pair[ 0 ].as_vec2() + Vec2::splat( 0.5 );
+            let b = pair[ 1 ].as_vec2() + Vec2::splat( 0.5 );
+            fill( a.min( b ) - half, a.max( b ) + half, RENDER_SOLUTION_COLOR );
+        }
+
+        ( width, height, pixels )
+    }
+
+    //PNG形式にする
+    pub fn render_png( &self, palette: &Palette, options: RenderOptions ) -> Result<Vec<u8>, String>
+    {   let ( width, height, pixels ) = self.render_rgba( palette, options );
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new( &mut png, width, height );
+        encoder.set_color( png::ColorType::Rgba );
+        encoder.set_depth( png::BitDepth::Eight );
+        encoder.write_header()
+            .and_then( | mut writer | writer.write_image_data( &pixels ) )
+            .map_err( | err | err.to_string() )?;
+
+        Ok ( png )
+    }
+
+    //SVG形式にする(1マスを1単位とし、表示サイズはcell_pixels倍)
+    pub fn render_svg( &self, palette: &Palette, options: RenderOptions ) -> String
+    {   let hex = | [ r, g, b, _ ]: [ u8; 4 ] | format!( "#{r:02x}{g:02x}{b:02x}" );
+        let px = options.cell_pixels.max( 1 );
+        let ( w, h ) = ( self.width, self.height );
+
+        let mut svg = String::new();
+        let _ = writeln!
+        (   svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {w} {h}" shape-rendering="crispEdges">"#,
+            w as u32 * px, h as u32 * px
+        );
+
+        //マス(横に同じ色が続く所は一つの矩形にまとめる)
+        let ( cells, solution, dead_ends ) = self.render_layers( palette, options );
+        for row in cells.chunks( w as usize )
+        {   let mut x = 0;
+            for run in row.chunk_by( | a, b | a.1 == b.1 )
+            {   let ( IVec2 { y, .. }, color ) = run[ 0 ];
+                let _ = writeln!
+                (   svg,
+                    r#"<rect x="{x}" y="{y}" width="{}" height="1" fill="{}"/>"#,
+                    run.len(), hex( color )
+                );
+                x += run.len();
+            }
+        }
+
+        //行き止りの印
+        let size = RENDER_DEAD_END_SIZE;
+        for cell in dead_ends
+        {   let ( x, y ) = ( cell.x as f32 + 0.5 - size / 2.0, cell.y as f32 + 0.5 - size / 2.0 );
+            let _ = writeln!
+            (   svg,
+                r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{}"/>"#,
+                hex( RENDER_DEAD_END_COLOR )
+            );
+        }
+
+        //解の経路
+        if ! solution.is_empty()
+        {   let points: Vec<_> = solution.iter()
+                .map( | cell | format!( "{},{}", cell.x as f32 + 0.5, cell.y as f32 + 0.5 ) )
+                .collect();
+            let _ = writeln!
+            (   svg,
+                r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{RENDER_SOLUTION_WIDTH}" stroke-linecap="square"/>"#,
+                points.join( " " ), hex( RENDER_SOLUTION_COLOR )
+            );
+        }
+        svg.push_str( "</svg>\n" );
+
+        svg
+    }
+
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,126 @@
+use super::*;
+use std::collections::{ BinaryHeap, VecDeque };
+use std::cmp::Reverse;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//距離場(あるマスから各マスまでの歩数、到達できないマスはNone)
+pub struct DistanceField
+{   width   : i32,
+    height  : i32,
+    distance: Vec<Option<u32>>,
+}
+
+impl DistanceField
+{   //指定のマスまでの歩数
+    pub fn get( &self, IVec2 { x, y }: IVec2 ) -> Option<u32>
+    {   if x < 0 || y < 0 || x >= self.width || y >= self.height { return None }
+        self.distance[ ( x * self.height + y ) as usize ]
+    }
+
+    //最も遠いマスとその歩数(同じ歩数ならx優先で左上のマス)
+    pub fn farthest( &self ) -> Option<( IVec2, u32 )>
+    {   self.iter().fold
+        (   None,
+            | max, ( cell, d ) | match max
+            {   Some ( ( _, max_d ) ) if max_d >= d => max,
+                _ => Some ( ( cell, d ) ),
+            }
+        )
+    }
+
+    //到達できるマスと歩数を列挙する
+    pub fn iter( &self ) -> impl Iterator<Item = ( IVec2, u32 )> + '_
+    {   let height = self.height;
+        self.distance.iter().enumerate().filter_map
+        (   move | ( i, d ) |
+            {   let cell = IVec2::new( i as i32 / height, i as i32 % height );
+                d.map( | d | ( cell, d ) )
+            }
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（経路探索）
+impl Map
+{   //指定のマスから全ての空地までの歩数を幅優先探索で求める
+    pub fn distance_field( &self, origin: IVec2 ) -> DistanceField
+    {   let mut field = DistanceField
+        {   width   : self.width,
+            height  : self.height,
+            distance: vec![ None; ( self.width * self.height ) as usize ],
+        };
+        if ! self.is_space( origin ) { return field }
+
+        let index = | IVec2 { x, y }: IVec2 | ( x * self.height + y ) as usize;
+        field.distance[ index( origin ) ] = Some ( 0 );
+        let mut queue = VecDeque::from( [ ( origin, 0 ) ] );
+
+        while let Some ( ( cell, d ) ) = queue.pop_front()
+        {   for news in NEWS
+            {   let next = cell + news;
+                if ! self.is_space( next ) || field.distance[ index( next ) ].is_some() { continue }
+                field.distance[ index( next ) ] = Some ( d + 1 );
+                queue.push_back( ( next, d + 1 ) );
+            }
+        }
+
+        field
+    }
+
+    //スタート地点から指定のマスまでの最短経路(両端を含む)
+    pub fn path_from_start( &self, goal: IVec2 ) -> Option<Vec<IVec2>>
+    {   self.find_path( self.start, goal )
+    }
+
+    //二つのマスの間の最短経路をA*で求める(両端を含む、到達できないならNone)
+    pub fn find_path( &self, from: IVec2, to: IVec2 ) -> Option<Vec<IVec2>>
+    {   if ! self.is_space( from ) || ! self.is_space( to ) { return None }
+
+        let index = | IVec2 { x, y }: IVec2 | ( x * self.height + y ) as usize;
+        let heuristic = | cell: IVec2 | //マンハッタン距離
+        {   let IVec2 { x, y } = ( cell - to ).abs();
+            ( x + y ) as u32
+        };
+        let len = ( self.width * self.height ) as usize;
+        let mut cost = vec![ u32::MAX; len ];
+        let mut came_from = vec![ None; len ];
+
+        //(推定総歩数, 歩数, x, y)の小さい順に取り出す
+        let mut open = BinaryHeap::new();
+        cost[ index( from ) ] = 0;
+        open.push( Reverse ( ( heuristic( from ), 0, from.x, from.y ) ) );
+
+        while let Some ( Reverse ( ( _, d, x, y ) ) ) = open.pop()
+        {   let cell = IVec2::new( x, y );
+            if cell == to { break }
+            if d > cost[ index( cell ) ] { continue } //古い候補
+
+            for news in NEWS
+            {   let next = cell + news;
+                if ! self.is_space( next ) || d + 1 >= cost[ index( next ) ] { continue }
+                cost[ index( next ) ] = d + 1;
+                came_from[ index( next ) ] = Some ( cell );
+                open.push( Reverse ( ( d + 1 + heuristic( next ), d + 1, next.x, next.y ) ) );
+            }
+        }
+        if cost[ index( to ) ] == u32::MAX { return None }
+
+        //ゴールから逆にたどる
+        let mut path = vec![ to ];
+        let mut cell = to;
+        while let Some ( prev ) = came_from[ index( cell ) ]
+        {   path.push( prev );
+            cell = prev;
+        }
+        path.reverse();
+
+        Some ( path )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
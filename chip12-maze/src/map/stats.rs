@@ -0,0 +1,162 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路の統計
+pub struct MapStats
+{   pub seed     : u64,          //乱数のシード
+    pub width    : i32,          //マップの横幅(外壁含む)
+    pub height   : i32,          //マップの縦幅(外壁含む)
+    pub generator: &'static str, //迷路作成アルゴリズム
+    pub braid    : u32,          //行き止りを解消する割合[%]
+    pub spaces   : usize,        //空地の数
+    pub reachable: usize,        //スタートから行ける空地の数
+    pub dead_ends: usize,        //行き止りの数
+    pub junctions: usize,        //三叉路の数
+    pub crossroads: usize,       //十字路の数
+    pub corridor : f32,          //通路(分岐から次の分岐か行き止りまで)の平均の長さ
+    pub river    : f32,          //空地のうち開口がちょうど二つのマス(直線か曲り角の通路)の割合(0.0～1.0)
+                                 //＜注意＞ 一般的なriver(通路の長さの偏り)とは違い、曲り角も直線も同じに数える
+    pub solution : Option<u32>,  //スタートからゴールまでの歩数
+    pub on_path  : Option<f32>,  //スタートから行ける空地のうち解の経路上のマスの割合(0.0～1.0)
+    pub decisions: Option<usize>, //解の経路上で道を選ぶマスの数
+}
+
+//統計を一行ずつ「名前: 値」の形で表示する
+impl std::fmt::Display for MapStats
+{   fn fmt( &self, f: &mut std::fmt::Formatter ) -> std::fmt::Result
+    {   let solution = self.solution.map_or( "unreachable".to_string(), | steps | steps.to_string() );
+        writeln!( f, "seed: {}"     , self.seed )?;
+        writeln!( f, "size: {}x{}"  , self.width, self.height )?;
+        writeln!( f, "algorithm: {}", self.generator )?;
+        writeln!( f, "braid: {}%"   , self.braid )?;
+        writeln!( f, "spaces: {}"   , self.spaces )?;
+        writeln!( f, "reachable: {}", self.reachable )?;
+        writeln!( f, "dead ends: {}", self.dead_ends )?;
+        writeln!( f, "junctions: {}", self.junctions )?;
+        writeln!( f, "crossroads: {}", self.crossroads )?;
+        writeln!( f, "mean corridor: {:.2}", self.corridor )?;
+        writeln!( f, "river: {:.2}" , self.river )?;
+        writeln!( f, "solution: {solution}" )?;
+        if let ( Some ( on_path ), Some ( decisions ) ) = ( self.on_path, self.decisions )
+        {   writeln!( f, "on path: {:.1}%", on_path * 100.0 )?;
+            writeln!( f, "decisions: {decisions}" )?;
+        }
+        match self.difficulty()
+        {   Some ( score ) => writeln!( f, "difficulty: {score:.1} ({})", Difficulty::from_score( score ) ),
+            None => writeln!( f, "difficulty: unreachable" ),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//Mapのメソッド（検査と統計）
+impl Map
+{   //迷路の統計を取る
+    pub fn stats( &self ) -> MapStats
+    {   let field = self.distance_field( self.start );
+        let spaces = self.cells().filter( | &cell | self.is_space( cell ) ).count();
+        let reachable = field.iter().count();
+        let count_sides = | sides | self.cells().filter( | &cell | self.is_space( cell ) && self.open_sides( cell ) == sides ).count();
+        let ratio = | n: usize, d: usize | if d == 0 { 0.0 } else { n as f32 / d as f32 };
+
+        //解の経路(長さと、道を選ぶマスの数)
+        let path = self.path_from_start( self.goal );
+        let on_path = path.as_ref().map( | path | ratio( path.len(), reachable ) );
+        let decisions = path.as_ref().map( | path | self.count_decisions( path ) );
+
+        MapStats
+        {   seed      : self.seed,
+            width     : self.width,
+            height    : self.height,
+            generator : self.generator_name(),
+            braid     : self.braid_percent,
+            spaces,
+            reachable,
+            dead_ends : self.dead_end_count(),
+            junctions : count_sides( 3 ),
+            crossroads: count_sides( 4 ),
+            corridor  : self.mean_corridor(),
+            river     : ratio( count_sides( 2 ), spaces ),
+            solution  : field.get( self.goal ),
+            on_path,
+            decisions,
+        }
+    }
+
+    //四方のうち空地の数
+    fn open_sides( &self, cell: IVec2 ) -> usize
+    {   NEWS.iter().filter( | &&news | self.is_space( cell + news ) ).count()
+    }
+
+    //通路の平均の長さ(分岐か行き止りから、次の分岐か行き止りまでの歩数)
+    //＜注意＞ 通路は両端から一回ずつ数えるが、平均は変わらない
+    fn mean_corridor( &self ) -> f32
+    {   let is_node = | cell | self.is_space( cell ) && self.open_sides( cell ) != 2;
+        let ( mut total, mut count ) = ( 0, 0 );
+
+        for node in self.cells().filter( | &cell | is_node( cell ) )
+        {   for news in NEWS
+            {   if ! self.is_space( node + news ) { continue }
+
+                //分岐か行き止りに着くまで一本道をたどる
+                let ( mut prev, mut cell, mut length ) = ( node, node + news, 1 );
+                while ! is_node( cell )
+                {   let Some ( next ) = NEWS.iter().map( | &news | cell + news )
+                        .find( | &next | next != prev && self.is_space( next ) ) else { break };
+                    ( prev, cell ) = ( cell, next );
+                    length += 1;
+                }
+                total += length;
+                count += 1;
+            }
+        }
+
+        if count == 0 { 0.0 } else { total as f32 / count as f32 }
+    }
+
+    //解の経路上で道を選ぶマスの数(スタートは二方向以上、途中は三方向以上が空地)
+    fn count_decisions( &self, path: &[ IVec2 ] ) -> usize
+    {   let Some ( ( _goal, path ) ) = path.split_last() else { return 0 };
+        path.iter().enumerate()
+            .filter( | &( i, &cell ) | self.open_sides( cell ) >= if i == 0 { 2 } else { 3 } )
+            .count()
+    }
+
+    //迷路として遊べるか検査し、問題点を列挙する(問題がなければ空)
+    pub fn validate( &self ) -> Vec<String>
+    {   let mut problems = Vec::new();
+
+        //外壁が閉じていること
+        let openings = self.cells()
+            .filter( | &cell | ! self.is_inside_inner( cell ) && ! self.is_wall( cell ) )
+            .count();
+        if openings > 0
+        {   problems.push( format!( "Outer wall has {openings} non-wall cells" ) );
+        }
+
+        //スタート地点とゴールが空地であること
+        for ( name, cell ) in [ ( "Start", self.start ), ( "Goal", self.goal ) ]
+        {   if ! self.is_space( cell )
+            {   problems.push( format!( "{name} ({}, {}) is not a space", cell.x, cell.y ) );
+            }
+        }
+
+        //ゴールと全ての空地にスタートから行けること
+        let stats = self.stats();
+        if stats.solution.is_none()
+        {   problems.push( "Goal is unreachable from start".to_string() );
+        }
+        if stats.reachable < stats.spaces
+        {   let count = stats.spaces - stats.reachable;
+            problems.push( format!( "{count} spaces are unreachable from start" ) );
+        }
+
+        problems
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
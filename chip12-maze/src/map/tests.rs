@@ -0,0 +1,854 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路をASCIIの行にする(壁は#、空地は.)
+fn to_ascii( map: &Map ) -> Vec<String>
+{   map.y_range().map
+    (   | y |
+        map.x_range()
+        .map( | x | if map.is_wall( IVec2::new( x, y ) ) { '#' } else { '.' } )
+        .collect()
+    )
+    .collect()
+}
+
+//ASCIIの行からMapを作る(#は壁、それ以外は空地。Sはスタート地点)
+fn from_ascii( lines: &[ &str ] ) -> Map
+{   let mut map = Map::new( lines[ 0 ].len() as i32, lines.len() as i32, 0 );
+    map.fill_walls();
+    for ( y, line ) in lines.iter().enumerate()
+    {   for ( x, char ) in line.chars().enumerate()
+        {   let cell = IVec2::new( x as i32, y as i32 );
+            if char != '#' { map.set_space( cell ) }
+            if char == 'S' { map.start = cell }
+        }
+    }
+    map
+}
+
+//縦横幅・シード・アルゴリズムを指定して迷路を作る
+fn make_map( width: i32, height: i32, seed: u64, generator: usize ) -> Map
+{   let mut map = Map::new( width, height, seed );
+    map.generator = generator;
+    map.generate();
+    map
+}
+
+//テスト用の色(壁とゴールの色、床の色)
+fn test_palette( wall: [ u8; 4 ], floor: [ u8; 4 ] ) -> Palette
+{   Palette { wall, wall_zero: wall, floor, goal: wall }
+}
+
+//FNV-1a(std::hashは版によって値が変わるので使わない)
+fn fnv1a( lines: &[ String ] ) -> u64
+{   lines.iter().flat_map( | line | line.bytes() ).fold
+    (   0xcbf2_9ce4_8422_2325,
+        | hash, byte | ( hash ^ byte as u64 ).wrapping_mul( 0x0100_0000_01b3 )
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//小さい迷路のレイアウトを固定する(穴掘り法)
+const GOLDEN_LAYOUTS: [ ( u64, [ &str; 11 ] ); 3 ] =
+[   (   1,
+        [   "#####################",
+            "#..#...##...#.#..#.##",
+            "##...#..###...##....#",
+            "#######..#..#.#####.#",
+            "###...##.#.##..#....#",
+            "##..#..#.#..##.##.###",
+            "#..###.#.####...#...#",
+            "#.##......#.###.##.##",
+            "#..########...#.....#",
+            "##..........#...#.#.#",
+            "#####################",
+        ]
+    ),
+    (   42,
+        [   "#####################",
+            "##....#..#.#.....#..#",
+            "#..##...##...###.#.##",
+            "#.#####.#..#.#...#..#",
+            "#...#...#.####.####.#",
+            "#####.#.#..##..#....#",
+            "#.....######..###.#.#",
+            "#.###.##...##.#.#.#.#",
+            "#...###..#..#.#...#.#",
+            "#.#.....###.....#.#.#",
+            "#####################",
+        ]
+    ),
+    (   1234567890,
+        [   "#####################",
+            "#...#.####.....#....#",
+            "#.#....##..###.#.#.##",
+            "#.##.#.#..##...###..#",
+            "#..###.#.#####.#...##",
+            "##..##.#.#.##..##.###",
+            "#..##..#...#..##....#",
+            "#.##..######.##..#.##",
+            "#.##.##...#...#.##..#",
+            "#..#....#...#....##.#",
+            "#####################",
+        ]
+    ),
+];
+
+//既定サイズの迷路のレイアウトのハッシュを固定する(全アルゴリズム、シード1234567890)
+const GOLDEN_HASHES: [ ( &str, u64 ); 8 ] =
+[   ( "DEPTH-FIRST" , 0xf086_cd01_6c6f_2703 ),
+    ( "PRIM"        , 0xc3ea_ef46_3057_4bb0 ),
+    ( "KRUSKAL"     , 0xdce7_90d2_2ee8_ad4a ),
+    ( "WILSON"      , 0x17f4_28f4_f343_57b8 ),
+    ( "ELLER"       , 0x7422_1d01_6f30_df3c ),
+    ( "DIVISION"    , 0x20f3_b042_8f96_4ce8 ),
+    ( "GROWING-TREE", 0x1705_fadd_bd78_df04 ),
+    ( "DUNGEON"     , 0xe076_3c73_5fb6_1818 ),
+];
+
+////////////////////////////////////////////////////////////////////////////////
+
+//同じシードなら同じ迷路になる
+#[test]
+fn same_seed_same_layout()
+{   for i in 0..GENERATORS.len()
+    {   let a = make_map( 31, 21, 777, i );
+        let b = make_map( 31, 21, 777, i );
+        assert_eq!( to_ascii( &a ), to_ascii( &b ), "{}", a.generator_name() );
+    }
+}
+
+//シードごとのレイアウトが変わっていない
+//＜注意＞ 失敗した場合、共有済みのシードで別の迷路ができてしまう
+#[test]
+fn golden_layouts()
+{   for ( seed, expected ) in GOLDEN_LAYOUTS
+    {   let map = make_map( 21, 11, seed, 0 );
+        assert_eq!( to_ascii( &map ), expected, "seed {seed}" );
+    }
+}
+
+#[test]
+fn golden_hashes()
+{   assert_eq!( GENERATORS.len(), GOLDEN_HASHES.len() );
+    for ( i, ( name, expected ) ) in GOLDEN_HASHES.into_iter().enumerate()
+    {   let map = make_map( 100, 100, 1234567890, i );
+        assert_eq!( map.generator_name(), name );
+        assert_eq!( fnv1a( &to_ascii( &map ) ), expected, "{name}" );
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//ダンジョンの部屋は指定の数と大きさで、スタート地点は最初の部屋の中にある
+#[test]
+fn dungeon_rooms()
+{   let make_dungeon = | room_count, room_size, seed |
+    {   let mut map = Map::new( 31, 31, seed );
+        map.set_room_count( room_count );
+        map.set_room_size( room_size );
+        map.fill_walls();
+        map.start = IVec2::new( 15, 15 );
+        map.set_space( map.start );
+        let mut state = Dungeon.start( &mut map );
+        while state.step( &mut map ) {}
+        map.place_goal();
+        map
+    };
+
+    //部屋が一つなら、通路はなく部屋だけ(5x5)
+    for seed in 1..10
+    {   let map = make_dungeon( 1..=1, 5..=5, seed );
+        let spaces: Vec<_> = map.cells().filter( | &cell | map.is_space( cell ) ).collect();
+        let ( min, max ) = ( spaces[ 0 ], spaces[ spaces.len() - 1 ] );
+        assert_eq!( ( spaces.len(), max - min ), ( 25, IVec2::splat( 4 ) ), "seed {seed}" );
+        assert!( spaces.contains( &map.start ) );
+    }
+
+    //偶数の大きさは奇数に切り上げる。部屋が多くても全てつながる
+    for seed in 1..10
+    {   let map = make_dungeon( 8..=8, 2..=4, seed );
+        assert!( map.validate().is_empty(), "seed {seed}: {:?}", map.validate() );
+        let is_room = | cell: IVec2 | ( 0..3 ).all( | dx | ( 0..3 ).all( | dy | map.is_space( cell + IVec2::new( dx, dy ) ) ) );
+        assert!( map.cells().any( is_room ), "seed {seed}" );
+    }
+
+    //範囲は下限を1以上にし、上限を下限に揃える
+    let mut map = Map::new( 31, 31, 1 );
+    map.set_room_count( -3..=0 );
+    map.set_room_size( RangeInclusive::new( 7, 5 ) );
+    assert_eq!( ( map.room_count(), map.room_size() ), ( 1..=1, 7..=7 ) );
+}
+
+//距離場と最短経路(ループのある小さなMap)
+const LOOPED_MAP: [ &str; 7 ] =
+[   "#########",
+    "#S......#",
+    "#.##.##.#",
+    "#.##.##.#",
+    "#.......#",
+    "#####.###",
+    "#.#######", //(1,6)はどこからも到達できない
+];
+
+#[test]
+fn distance_field_counts_steps()
+{   let map = from_ascii( &LOOPED_MAP );
+    let field = map.distance_field( map.start );
+
+    assert_eq!( field.get( map.start ), Some ( 0 ) );
+    assert_eq!( field.get( IVec2::new( 7, 1 ) ), Some ( 6 ) );
+    assert_eq!( field.get( IVec2::new( 4, 4 ) ), Some ( 6 ) );
+    assert_eq!( field.get( IVec2::new( 5, 5 ) ), Some ( 8 ) );
+    assert_eq!( field.get( IVec2::new( 1, 6 ) ), None ); //到達できない
+    assert_eq!( field.get( IVec2::new( 0, 0 ) ), None ); //壁
+    assert_eq!( field.get( IVec2::new( -1, 99 ) ), None ); //範囲外
+    assert_eq!( field.farthest(), Some ( ( IVec2::new( 7, 4 ), 9 ) ) );
+}
+
+#[test]
+fn path_is_shortest_and_connected()
+{   let map = from_ascii( &LOOPED_MAP );
+    let goal = IVec2::new( 5, 5 );
+    let path = map.path_from_start( goal ).unwrap();
+
+    assert_eq!( path.first(), Some ( &map.start ) );
+    assert_eq!( path.last(), Some ( &goal ) );
+    assert_eq!( path.len() as u32 - 1, map.distance_field( map.start ).get( goal ).unwrap() );
+    assert!( path.windows( 2 ).all( | w | NEWS.iter().any( | &news | w[ 0 ] + news == w[ 1 ] ) ) );
+    assert!( path.iter().all( | &cell | map.is_space( cell ) ) );
+
+    assert_eq!( map.path_from_start( IVec2::new( 1, 6 ) ), None ); //到達できない
+    assert_eq!( map.path_from_start( IVec2::new( 0, 0 ) ), None ); //壁
+}
+
+//ループのある迷路でもA*の歩数が幅優先探索と一致する
+#[test]
+fn astar_matches_bfs_on_braided_mazes()
+{   for i in 0..GENERATORS.len()
+    {   let mut map = Map::new( 41, 31, 99 );
+        map.generator = i;
+        map.braid_percent = 50;
+        map.generate();
+
+        let field = map.distance_field( map.start );
+        for ( cell, d ) in field.iter().step_by( 7 )
+        {   let path = map.path_from_start( cell ).unwrap();
+            assert_eq!( path.len() as u32 - 1, d, "{} {cell}", map.generator_name() );
+        }
+    }
+}
+
+//ループ化すると、指定の割合の行き止りが実際に解消される(100%なら一つも残らない)
+//一度掘ると周りの行き止りもまとめて解消されることがあるので、残る数は目標より最大3つ少ない
+#[test]
+fn braid_honours_percent()
+{   for ( generator, algorithm ) in GENERATORS.iter().enumerate()
+    {   for seed in 0..4
+        {   for percent in [ 25, 50, 100 ]
+            {   let mut map = make_map( 51, 41, seed, generator );
+                let deadends = map.dead_end_count();
+                let goal = deadends - deadends * percent as usize / 100;
+                map.braid( percent );
+                let remaining = map.dead_end_count();
+                let name = algorithm.name();
+                assert!( remaining <= goal && remaining + 3 >= goal, "{name} seed {seed} {percent}%: {remaining} left of {deadends}" );
+                if percent == 100 { assert_eq!( remaining, 0, "{name} seed {seed}" ) }
+            }
+        }
+    }
+}
+
+//ゴールはスタートから最も遠いマスに置かれる
+#[test]
+fn goal_is_farthest_cell()
+{   for i in 0..GENERATORS.len()
+    {   let map = make_map( 41, 31, 5, i );
+        let field = map.distance_field( map.start );
+        let ( _, max ) = field.farthest().unwrap();
+        assert_eq!( field.get( map.goal ), Some ( max ), "{}", map.generator_name() );
+    }
+}
+
+//最低歩数を満たすまで作り直す(満たせない場合は最後の迷路のまま)
+#[test]
+fn goal_respects_min_distance()
+{   let mut map = Map::new( 21, 11, 1 );
+    map.goal_min_distance = 25;
+    map.generate();
+    assert!( map.distance_field( map.start ).get( map.goal ).unwrap() >= 25 );
+
+    map.goal_min_distance = u32::MAX;
+    map.generate();
+    assert!( map.is_space( map.goal ) );
+}
+
+//壁のメッシュの面の数は、原点以外の壁の上面と、壁でない隣に向いた側面の合計になる
+#[test]
+fn wall_mesh_counts_match_map()
+{   for ( width, height ) in [ ( 21, 11 ), ( 40, 33 ) ]
+    {   let map = make_map( width, height, 7, 0 );
+
+        //マップから数えた面の数
+        let mut expected = 0;
+        for x in map.x_range()
+        {   for y in map.y_range()
+            {   let cell = IVec2::new( x, y );
+                if cell == IVec2::ZERO || ! map.is_wall( cell ) { continue }
+                expected += 1;
+                expected += NEWS.iter()
+                    .filter( | &&news | ! map.is_inside( cell + news ) || map.is_space( cell + news ) )
+                    .count();
+            }
+        }
+
+        //チャンクごとのメッシュの頂点と面の数
+        let ( mut vertices, mut faces ) = ( 0, 0 );
+        for chunk in map.wall_chunk_iter()
+        {   let data = map.wall_chunk_data( chunk );
+            let indices = data.indices.len();
+            assert_eq!( indices % 6, 0 );
+            assert_eq!( data.positions.len(), indices / 6 * 4 );
+            assert_eq!( data.normals.len(), data.positions.len() );
+            assert_eq!( data.uvs.len(), data.positions.len() );
+            vertices += data.positions.len();
+            faces    += indices / 6;
+        }
+        assert_eq!( faces, expected, "{width}x{height}" );
+        assert_eq!( vertices, expected * 4, "{width}x{height}" );
+    }
+}
+
+//ASCII形式で保存して読み込むと同じ迷路になる
+#[test]
+fn ascii_round_trip()
+{   for i in 0..GENERATORS.len()
+    {   let map = make_map( 31, 21, 3, i );
+        let text = map.to_ascii();
+        let loaded = Map::from_ascii( &text ).unwrap();
+        assert_eq!( to_ascii( &loaded ), to_ascii( &map ), "{}", map.generator_name() );
+        assert_eq!( ( loaded.seed, loaded.start, loaded.goal ), ( map.seed, map.start, map.goal ) );
+        assert_eq!( loaded.to_ascii(), text );
+    }
+}
+
+//手書きの迷路ファイル(ゴール省略時はスタートから最も遠いマス)
+#[test]
+fn ascii_hand_authored()
+{   let text = "start: 1 1\n\n#######\n#.....#\n#####.#\n#.....#\n#######\n";
+    let map = Map::from_ascii( text ).unwrap();
+    assert_eq!( ( map.width, map.height, map.seed ), ( 7, 5, 0 ) );
+    assert_eq!( map.goal, IVec2::new( 1, 3 ) );
+}
+
+//不正な迷路ファイルはエラーになる
+#[test]
+fn ascii_errors()
+{   let grid = "#####\n#...#\n#.#.#\n#...#\n#####\n";
+    let cases =
+    [   grid.to_string(),                             //スタートがない
+        format!( "start: 0 0\n{grid}" ),              //スタートが壁
+        format!( "start: 1 1\ngoal: 2 2\n{grid}" ),   //ゴールが壁
+        format!( "start: 1 1\nsize: 5\n{grid}" ),     //不明なヘッダー
+        format!( "start: 1\n{grid}" ),                //座標が足りない
+        format!( "start: 1 1\n{grid}seed: 1\n" ),     //迷路の後のヘッダー
+        format!( "start: 1 1\n{grid}#...\n" ),        //行の長さが違う
+        format!( "start: 1 1\n{}", grid.replace( '.', "o" ) ), //不正な文字
+        "start: 1 1\n###\n#.#\n###\n".to_string(),  //小さすぎる
+        "start: 1 1\n".to_string(),                   //迷路がない
+    ];
+    for text in cases
+    {   assert!( Map::from_ascii( &text ).is_err(), "{text}" );
+    }
+}
+
+//構造化した形式(RON/JSON)で保存して読み込むと、フラグのビットまで同じになる
+#[test]
+fn structured_round_trip()
+{   let bits = | map: &Map | -> Vec<u128>
+    {   map.matrix.iter().flatten().map( | flag | flag.bits() ).collect()
+    };
+
+    for format in [ MapFormat::Ron, MapFormat::Json ]
+    {   for i in 0..GENERATORS.len()
+        {   let mut map = Map::new( 31, 21, 3 );
+            map.generator = i;
+            map.braid_percent = 25;
+            map.goal_min_distance = 10;
+            map.set_room_count( 3..=5 );
+            map.set_room_size( 5..=7 );
+            map.generate();
+            assert!( i != 0 || map.matrix.iter().flatten().any( | flag | flag.has( Overlay::DeadEnd ) ) );
+
+            let text = map.serialize( format ).unwrap();
+            let loaded = Map::deserialize( &text, format ).unwrap();
+            assert_eq!( bits( &loaded ), bits( &map ), "{format:?} {}", map.generator_name() );
+            assert_eq!( ( loaded.seed, loaded.start, loaded.goal ), ( map.seed, map.start, map.goal ) );
+            assert_eq!( ( loaded.generator, loaded.braid_percent, loaded.goal_min_distance ), ( i, 25, 10 ) );
+            assert_eq!( ( loaded.room_count(), loaded.room_size() ), ( 3..=5, 5..=7 ) );
+            assert_eq!( loaded.serialize( format ).unwrap(), text );
+        }
+    }
+
+    //版1のファイルには部屋の範囲がないので既定値になる
+    let map = make_map( 11, 11, 1, 0 );
+    let mut json: serde_json::Value = serde_json::from_str( &map.serialize( MapFormat::Json ).unwrap() ).unwrap();
+    let object = json.as_object_mut().unwrap();
+    object.insert( "version".to_string(), 1.into() );
+    object.remove( "room_count" );
+    object.remove( "room_size" );
+    let loaded = Map::deserialize( &json.to_string(), MapFormat::Json ).unwrap();
+    assert_eq!( ( loaded.room_count(), loaded.room_size() ), ( DUNGEON_ROOM_COUNT, DUNGEON_ROOM_SIZE ) );
+    assert_eq!( loaded.to_ascii(), map.to_ascii() );
+}
+
+//版が違う、または中身が不正な構造化ファイルはエラーになる
+#[test]
+fn structured_errors()
+{   let map = make_map( 11, 11, 1, 0 );
+    let text = map.serialize( MapFormat::Json ).unwrap();
+    let cases =
+    [   text.replace( "\"version\": 2", "\"version\": 3" ),                     //新しい版
+        text.replace( "\"version\": 2", "\"version\": 0" ),                     //古い版
+        text.replace( "\"DEPTH-FIRST\"", "\"UNKNOWN\"" ),                        //不明なアルゴリズム
+        text.replacen( "\"2 2 2", "\"3 2 2", 1 ),                               //地形が二つ
+        text.replacen( "\"2 2 2", "\"x 2 2", 1 ),                               //16進数でない
+        text.replacen( "\"2 2 2", "\"2 2", 1 ),                                 //マスが足りない
+        text.replace( "\"height\": 11", "\"height\": 12" ),                     //行が足りない
+    ];
+    for text in cases
+    {   let error = Map::deserialize( &text, MapFormat::Json ).err();
+        assert!( error.is_some(), "{text}" );
+    }
+
+    let error = Map::deserialize( "( version: 3 )", MapFormat::Ron ).err().unwrap();
+    assert!( error.contains( "newer" ), "{error}" );
+    assert_eq!( MapFormat::from_fname( "a/maze.RON" ), MapFormat::Ron );
+    assert_eq!( MapFormat::from_fname( "maze.txt" ), MapFormat::Ascii );
+}
+
+//フラグは地形と別に立てて消せる。16進数の表記は迷路ファイルの版1と同じ
+#[test]
+fn typed_flags()
+{   let mut flag = Flag::new( Terrain::Space );
+    flag.set( Overlay::DeadEnd );
+    flag.set( Overlay::Door );
+    assert!( flag.has( Overlay::DeadEnd ) && flag.has( Overlay::Door ) && ! flag.has( Overlay::Item ) );
+    assert_eq!( format!( "{flag:?}" ), "Space | DeadEnd | Door" );
+
+    //地形を変えてもフラグは残る
+    flag.set_terrain( Terrain::Wall );
+    flag.clear( Overlay::Door );
+    assert_eq!( ( flag.terrain(), flag.overlays().collect::<Vec<_>>() ), ( Terrain::Wall, vec![ Overlay::DeadEnd ] ) );
+
+    //版1の空地＋行き止りは5。知らないビットも残す
+    let old: Flag = "5".parse().unwrap();
+    assert_eq!( ( old.terrain(), old.has( Overlay::DeadEnd ) ), ( Terrain::Space, true ) );
+    let future: Flag = "1000000000000000000000000000001".parse().unwrap();
+    assert_eq!( format!( "{future:x}" ), "1000000000000000000000000000001" );
+    assert_eq!( format!( "{future:?}" ), "Space | 0x1000000000000000000000000000000" );
+    assert!( "3".parse::<Flag>().is_err() );
+
+    //シリアライズは16進数の文字列
+    let json = serde_json::to_string( &flag ).unwrap();
+    assert_eq!( json, "\"6\"" );
+    assert_eq!( serde_json::from_str::<Flag>( &json ).unwrap(), flag );
+
+    //Mapのマスに立てて消す(掘ってもフラグは消えない)
+    let mut map = make_map( 11, 11, 1, 1 );
+    let cell = map.start;
+    map.set_overlay( cell, Overlay::Visited );
+    map.set_space( cell );
+    assert!( map.has_overlay( cell, Overlay::Visited ) );
+    map.clear_overlay_all( Overlay::Visited );
+    assert!( ! map.has_overlay( cell, Overlay::Visited ) );
+    assert!( ! map.has_overlay( IVec2::new( -1, 0 ), Overlay::Visited ) );
+    assert_eq!( map.terrain( IVec2::new( -1, 0 ) ), Terrain::Wall );
+    assert_eq!( map.flag( IVec2::new( -1, 0 ) ), None );
+}
+
+//OBJ形式のエクスポートは3D表示と同じ面の数になる
+#[test]
+fn obj_export_matches_meshes()
+{   let map = make_map( 21, 11, 1, 0 );
+    let palette = test_palette( [ 255, 0, 0, 255 ], [ 255, 0, 0, 255 ] );
+    let ( obj, mtl ) = map.export_obj( &palette, "maze.mtl" );
+
+    //面の数(壁・床はチャンクの合計、原点の壁とゴールは5面ずつ)
+    let faces = | data: MeshData | data.indices.len() / 3;
+    let expected = map.wall_chunk_iter()
+        .map( | chunk | faces( map.wall_chunk_data( chunk ) ) + faces( map.floor_chunk_data( chunk ) ) )
+        .sum::<usize>()
+        + faces( map.wall_zero_data() ) + faces( map.goal_data() );
+    let count = | prefix: &str | obj.lines().filter( | line | line.starts_with( prefix ) ).count();
+    assert_eq!( count( "f " ), expected );
+    assert_eq!( count( "v " ), expected / 2 * 4 );
+    assert_eq!( count( "v " ), count( "vt " ) );
+    assert_eq!( count( "v " ), count( "vn " ) );
+    assert_eq!( count( "usemtl " ), 4 );
+    assert_eq!( mtl.lines().filter( | line | line.starts_with( "Kd 1.0000 0.0000 0.0000" ) ).count(), 4 );
+
+    //頂点番号は範囲内
+    let max = count( "v " );
+    for line in obj.lines().filter( | line | line.starts_with( "f " ) )
+    {   for index in line[ 2.. ].split( [ ' ', '/' ] )
+        {   let index: usize = index.parse().unwrap();
+            assert!( ( 1..=max ).contains( &index ) );
+        }
+    }
+}
+
+//アルゴリズムは名前で探せる(大文字小文字は区別しない)
+#[test]
+fn generators_are_found_by_name()
+{   for ( i, name ) in generator_names().into_iter().enumerate()
+    {   assert_eq!( find_generator( name ), Some ( i ) );
+        assert_eq!( find_generator( &name.to_ascii_lowercase() ), Some ( i ) );
+    }
+    assert_eq!( find_generator( "no-such-maze" ), None );
+}
+
+//作った迷路は検査を通り、統計は迷路と一致する
+#[test]
+fn generated_maps_are_valid()
+{   for i in 0..GENERATORS.len()
+    {   let map = make_map( 31, 21, 5, i );
+        assert!( map.validate().is_empty(), "{}: {:?}", map.generator_name(), map.validate() );
+
+        let stats = map.stats();
+        let spaces = to_ascii( &map ).iter().flat_map( | line | line.chars() ).filter( | &c | c != '#' ).count();
+        assert_eq!( stats.spaces, spaces );
+        assert_eq!( stats.reachable, spaces );
+        assert_eq!( stats.dead_ends, map.dead_end_count() );
+        assert_eq!( stats.solution, map.distance_field( map.start ).get( map.goal ) );
+        assert!( stats.to_string().contains( &format!( "algorithm: {}", map.generator_name() ) ) );
+    }
+}
+
+//外壁の穴、行けない空地、行けないゴールは問題として報告される
+#[test]
+fn broken_maps_are_invalid()
+{   let text = "start: 1 1\ngoal: 3 1\n#####\n#.#.#\n#####\n#...#\n#####\n";
+    let map = Map::from_ascii( text ).unwrap();
+    let problems = map.validate();
+    assert!( problems.iter().any( | p | p.contains( "Goal is unreachable" ) ), "{problems:?}" );
+    assert!( problems.iter().any( | p | p.contains( "4 spaces are unreachable" ) ), "{problems:?}" );
+
+    let text = "start: 1 1\n#####\n#...#\n#...#\n#...#\n##.##\n";
+    let map = Map::from_ascii( text ).unwrap();
+    assert_eq!( map.validate(), vec![ "Outer wall has 1 non-wall cells".to_string() ] );
+}
+
+//分岐、通路、解の経路の統計を小さい迷路で確かめる
+#[test]
+fn stats_measure_junctions_and_corridors()
+{   let text = "start: 1 1\ngoal: 1 3\n#######\n#.....#\n#.#.#.#\n#.#...#\n#######\n";
+    let map = Map::from_ascii( text ).unwrap();
+    let stats = map.stats();
+
+    assert_eq!( stats.spaces, 12 );
+    assert_eq!( stats.dead_ends, 1 );
+    assert_eq!( stats.junctions, 1 );
+    assert_eq!( stats.crossroads, 0 );
+    assert_eq!( stats.corridor, 6.0 ); //4歩の通路と、三叉路に戻る8歩のループ(両方向から数える)
+    assert_eq!( stats.river, 10.0 / 12.0 );
+    assert_eq!( stats.solution, Some ( 2 ) );
+    assert_eq!( stats.on_path, Some ( 3.0 / 12.0 ) );
+    assert_eq!( stats.decisions, Some ( 1 ) ); //スタートで上下左右のどちらへ行くか
+    assert!( stats.to_string().contains( "difficulty: " ) );
+}
+
+//ループを作ると易しくなり、難しさを指定すればその段階の迷路ができる
+#[test]
+fn difficulty_levels()
+{   for seed in 1..4
+    {   let mut map = Map::new( 51, 51, seed );
+        map.generate();
+        let perfect = map.stats().difficulty().unwrap();
+        map.set_braid_percent( 100 );
+        map.generate();
+        let braided = map.stats().difficulty().unwrap();
+        assert!( braided < perfect, "seed {seed}: {braided} >= {perfect}" );
+    }
+
+    //シードだけを変えて探す(アルゴリズムとループ化の割合はそのまま)
+    let settings = [ ( Difficulty::Easy, 0, 100 ), ( Difficulty::Medium, 1, 0 ), ( Difficulty::Hard, 0, 0 ) ];
+    for ( level, generator, braid ) in settings
+    {   let mut map = Map::new( 31, 31, 7 );
+        map.set_generator( generator );
+        map.set_braid_percent( braid );
+        assert!( map.generate_with_difficulty( level ), "{level}" );
+        assert_eq!( map.stats().level(), Some ( level ) );
+        assert_eq!( ( map.generator(), map.braid_percent(), map.tuned_from() ), ( generator, braid, None ) );
+        assert!( map.validate().is_empty() );
+
+        assert_eq!( Difficulty::find( &level.name().to_ascii_lowercase() ), Some ( level ) );
+    }
+    assert_eq!( Difficulty::find( "impossible" ), None );
+
+    //同じ設定とシードなら同じ迷路になる
+    let mut map = Map::new( 31, 31, 7 );
+    map.set_braid_percent( 100 );
+    assert!( map.generate_with_difficulty( Difficulty::Easy ) );
+    let mut other = Map::new( 31, 31, 7 );
+    other.set_braid_percent( 100 );
+    assert!( other.generate_with_difficulty( Difficulty::Easy ) );
+    assert_eq!( ( map.seed(), map.to_ascii() ), ( other.seed(), other.to_ascii() ) );
+
+    //変えてよい場合は、変えたことと元の設定が分かる(選び直したら消える)
+    let mut map = Map::new( 31, 31, 7 );
+    assert!( map.generate_with_difficulty_tuned( Difficulty::Easy ) );
+    assert_eq!( map.stats().level(), Some ( Difficulty::Easy ) );
+    assert_eq!( map.tuned_from(), Some ( ( 0, 0 ) ) );
+    assert_ne!( ( map.generator(), map.braid_percent() ), ( 0, 0 ) );
+    assert!( map.generate_with_difficulty_tuned( Difficulty::Hard ) );
+    assert_eq!( ( map.generator(), map.braid_percent(), map.tuned_from() ), ( 0, 0, None ) );
+    map.set_braid_percent( 50 );
+    assert_eq!( map.tuned_from(), None );
+}
+
+//ソフトウェアで描いた画像は迷路と同じ並びになる(GPUなしで動く)
+#[test]
+fn render_image_matches_map()
+{   let map = make_map( 21, 11, 1, 0 );
+    let palette = test_palette( [ 255, 255, 255, 255 ], [ 0, 0, 0, 255 ] );
+    let px = 4;
+
+    //PNGを読み戻して、各マスの中心の色を確かめる
+    let options = RenderOptions { cell_pixels: px, ..Default::default() };
+    let png = map.render_png( &palette, options ).unwrap();
+    let mut reader = png::Decoder::new( png.as_slice() ).read_info().unwrap();
+    let mut pixels = vec![ 0; reader.output_buffer_size() ];
+    let info = reader.next_frame( &mut pixels ).unwrap();
+    assert_eq!( ( info.width, info.height ), ( 21 * px, 11 * px ) );
+    for ( y, line ) in to_ascii( &map ).iter().enumerate()
+    {   for ( x, char ) in line.chars().enumerate()
+        {   let cell = IVec2::new( x as i32, y as i32 );
+            if cell == map.start || cell == map.goal { continue }
+            let i = ( ( ( y as u32 * px + px / 2 ) * info.width + x as u32 * px + px / 2 ) * 4 ) as usize;
+            let expected = if char == '#' { 255 } else { 0 };
+            assert_eq!( pixels[ i ], expected, "{cell}" );
+        }
+    }
+
+    //重ねて描くと、解の経路と行き止りの色が増える
+    let options = RenderOptions { cell_pixels: px, solution: true, dead_ends: true };
+    let ( _, _, overlay ) = map.render_rgba( &palette, options );
+    let count = | color: [ u8; 4 ] | overlay.chunks( 4 ).filter( | rgba | *rgba == color ).count();
+    assert!( count( render::RENDER_SOLUTION_COLOR ) > 0 );
+    assert!( count( render::RENDER_DEAD_END_COLOR ) > 0 );
+
+    //SVGは各行の矩形と経路を含む
+    let svg = map.render_svg( &palette, options );
+    assert!( svg.starts_with( "<svg" ) && svg.ends_with( "</svg>\n" ) );
+    assert_eq!( svg.matches( "<polyline" ).count(), 1 );
+    assert!( svg.matches( "<rect" ).count() >= 11 );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//性質テスト(ランダムなシード・縦横幅・アルゴリズムで不変条件を確かめる)
+//失敗すると、縦横幅とシードを小さくした最小の反例が表示される
+use quickcheck::{ Arbitrary, Gen, QuickCheck, TestResult };
+
+//一つの性質を試す回数と、縦横幅の上限
+const PROPERTY_TESTS     : u64 = 200;
+const PROPERTY_GRIDS_MAX : i32 = 64;
+
+//迷路を作る条件
+#[derive( Clone, Debug )]
+struct MazeParams
+{   seed     : u64,
+    width    : i32,
+    height   : i32,
+    generator: usize,
+    braid    : u32,
+}
+
+impl Arbitrary for MazeParams
+{   fn arbitrary( g: &mut Gen ) -> Self
+    {   let grids = | g: &mut Gen | MAP_GRIDS_MIN + ( u32::arbitrary( g ) % ( PROPERTY_GRIDS_MAX - MAP_GRIDS_MIN + 1 ) as u32 ) as i32;
+        MazeParams
+        {   seed     : u32::arbitrary( g ) as u64,
+            width    : grids( g ),
+            height   : grids( g ),
+            generator: usize::arbitrary( g ) % GENERATORS.len(),
+            braid    : *g.choose( &[ 0, 0, 0, 25, 50, 100 ] ).unwrap(),
+        }
+    }
+
+    //縦横幅を下限へ、シードを0へ近づける
+    fn shrink( &self ) -> Box<dyn Iterator<Item = Self>>
+    {   let mut smaller = Vec::new();
+        let half = | n: i32 | MAP_GRIDS_MIN + ( n - MAP_GRIDS_MIN ) / 2;
+        if self.width  > MAP_GRIDS_MIN { smaller.push( MazeParams { width : half( self.width  ), ..self.clone() } ) }
+        if self.height > MAP_GRIDS_MIN { smaller.push( MazeParams { height: half( self.height ), ..self.clone() } ) }
+        if self.seed   > 0             { smaller.push( MazeParams { seed  : self.seed / 2     , ..self.clone() } ) }
+        Box::new( smaller.into_iter() )
+    }
+}
+
+impl MazeParams
+{   fn make_map( &self ) -> Map
+    {   let mut map = Map::new( self.width, self.height, self.seed );
+        map.generator = self.generator;
+        map.braid_percent = self.braid;
+        map.generate();
+        map
+    }
+}
+
+//性質を試す
+fn check_property( property: fn( MazeParams ) -> TestResult )
+{   QuickCheck::new().tests( PROPERTY_TESTS ).quickcheck( property );
+}
+
+//空地と、隣り合う空地の組(通路)の数
+fn count_spaces_and_links( map: &Map ) -> ( usize, usize )
+{   let spaces: Vec<_> = map.cells().filter( | &cell | map.is_space( cell ) ).collect();
+    let links = spaces.iter()
+        .map( | &cell | [ News::East, News::South ].iter().filter( | &&news | map.is_space( cell + news ) ).count() )
+        .sum();
+    ( spaces.len(), links )
+}
+
+//全ての空地にスタートから行ける
+#[test]
+fn property_all_spaces_reachable()
+{   fn property( params: MazeParams ) -> TestResult
+    {   let map = params.make_map();
+        let field = map.distance_field( map.start );
+        let unreachable = map.cells().find( | &cell | map.is_space( cell ) && field.get( cell ).is_none() );
+        TestResult::from_bool( unreachable.is_none() )
+    }
+    check_property( property );
+}
+
+//ループ化しなければ、迷路は木になる(ループも2x2の広場もない。ダンジョンは除く)
+//連結なので「通路の数 = 空地の数 - 1」と同値
+#[test]
+fn property_no_loops_without_braid()
+{   fn property( params: MazeParams ) -> TestResult
+    {   if params.braid > 0 || ! GENERATORS[ params.generator ].is_perfect() { return TestResult::discard() }
+        let map = params.make_map();
+        let ( spaces, links ) = count_spaces_and_links( &map );
+        TestResult::from_bool( links + 1 == spaces )
+    }
+    check_property( property );
+}
+
+//ループ化すると、通路は増えるだけ(木に辺を足したもの)
+#[test]
+fn property_braid_only_adds_loops()
+{   fn property( params: MazeParams ) -> TestResult
+    {   let map = params.make_map();
+        let ( spaces, links ) = count_spaces_and_links( &map );
+        TestResult::from_bool( links + 1 >= spaces )
+    }
+    check_property( property );
+}
+
+//外壁は壊されない
+#[test]
+fn property_outer_ring_is_solid()
+{   fn property( params: MazeParams ) -> TestResult
+    {   let map = params.make_map();
+        let hole = map.cells().find( | &cell | ! map.is_inside_inner( cell ) && ! map.is_wall( cell ) );
+        TestResult::from_bool( hole.is_none() )
+    }
+    check_property( property );
+}
+
+//穴掘り法では、後戻りで離れたマス(スタート地点以外の全ての空地)にだけ行き止りのフラグが立つ
+//他のアルゴリズムはフラグを使わない。ループ化で掘った壁にはフラグがない
+#[test]
+fn property_deadend_flags_mark_backtracked_cells()
+{   fn property( mut params: MazeParams ) -> TestResult
+    {   params.braid = 0;
+        let map = params.make_map();
+        let is_depth_first = GENERATORS[ params.generator ].name() == DepthFirst.name();
+        let wrong = map.cells().find
+        (   | &cell |
+            {   let expected = is_depth_first && map.is_space( cell ) && cell != map.start;
+                map.is_deadend( cell ) != expected
+            }
+        );
+        TestResult::from_bool( wrong.is_none() )
+    }
+    check_property( property );
+}
+
+//一歩ずつ最後まで作った迷路は、一度に作った迷路とフラグのビットまで同じになる
+//変わるマスは外壁の内側だけで、先頭は最後に変わったマス。後戻りの手順は行き止りのフラグと一致する
+fn dig_matches_generate( width: i32, height: i32, seed: u64, generator: usize, braid: u32 ) -> bool
+{   let mut expected = Map::new( width, height, seed );
+    expected.generator = generator;
+    expected.braid_percent = braid;
+    let mut map = expected.clone();
+    expected.generate();
+
+    let mut digger = map.digger();
+    let mut backtracked = Vec::new();
+    while let Some ( steps ) = digger.step( &mut map )
+    {   if steps.iter().any( | step | ! map.is_inside_inner( step.cell() ) ) { return false }
+        if steps.last().is_some_and( | &step | digger.head() != Some ( step ) ) { return false }
+        for step in steps
+        {   if let DigStep::Backtrack ( cell ) = step { backtracked.push( cell ) }
+        }
+    }
+    let bits = | map: &Map | map.matrix.iter().flatten().map( | flag | flag.bits() ).collect::<Vec<_>>();
+    let mut flagged: Vec<_> = map.cells().filter( | &cell | map.is_deadend( cell ) ).collect();
+    backtracked.sort_by_key( | cell | ( cell.x, cell.y ) );
+    flagged.sort_by_key( | cell | ( cell.x, cell.y ) );
+
+    digger.is_finished() && bits( &map ) == bits( &expected ) && map.goal == expected.goal && backtracked == flagged
+}
+
+//全てのアルゴリズムで、一歩ずつ最後まで作ると一度に作った迷路と同じになる
+#[test]
+fn digger_matches_generate()
+{   for ( generator, algorithm ) in GENERATORS.iter().enumerate()
+    {   for seed in 0..4
+        {   for braid in [ 0, 50, 100 ]
+            {   assert!( dig_matches_generate( 31, 21, seed, generator, braid ), "{} seed {seed} braid {braid}", algorithm.name() );
+            }
+        }
+    }
+}
+
+//ランダムな縦横幅でも、一歩ずつ作ると一度に作った迷路と同じになる
+#[test]
+fn property_digger_matches_generate()
+{   fn property( params: MazeParams ) -> TestResult
+    {   TestResult::from_bool( dig_matches_generate( params.width, params.height, params.seed, params.generator, params.braid ) )
+    }
+    check_property( property );
+}
+
+//作り始めの迷路はスタート地点だけが空地で、途中で止めても続きから最後まで作れる
+#[test]
+fn digger_resumes()
+{   let expected = make_map( 21, 11, 1, 0 );
+    let mut map = expected.clone();
+    let mut digger = map.digger();
+    assert_eq!( count_spaces_and_links( &map ), ( 1, 0 ) );
+    assert_eq!( ( digger.head(), map.goal ), ( None, map.start ) );
+    assert!( ! digger.is_finished() && ! digger.is_goal_ok() );
+
+    //途中まで進めて止める(作りかけの迷路はまだ木の一部)
+    for _ in 0..10 { digger.step( &mut map ); }
+    let ( spaces, links ) = count_spaces_and_links( &map );
+    assert!( spaces > 1 && links + 1 == spaces && ! digger.is_finished() );
+    assert!( digger.head().is_some_and( | step | map.is_space( step.cell() ) ) );
+
+    //残りをまとめて進める
+    assert!( digger.finish( &mut map ) );
+    assert_eq!( to_ascii( &map ), to_ascii( &expected ) );
+    assert_eq!( map.goal, expected.goal );
+}
+
+////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,60 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//四方を表す列挙型
+#[derive( Default, Clone, Copy, PartialEq, Eq, Debug )]
+pub enum News { #[default] North, East, West, South }
+
+impl News
+{   //右を向く、左を向く、後ろを向く
+    pub fn turn_right( self ) -> Self
+    {   match self
+        {   News::North => News::East,
+            News::East  => News::South,
+            News::South => News::West,
+            News::West  => News::North,
+        }
+    }
+    pub fn turn_left( self ) -> Self { self.turn_right().turn_right().turn_right() }
+    pub fn reverse  ( self ) -> Self { self.turn_right().turn_right() }
+
+    //XZ平面上の向き(ラジアン) Northが0.0で時計回り
+    pub fn yaw( self ) -> f32
+    {   match self
+        {   News::North => 0.0,
+            News::East  => PI * 0.5,
+            News::South => PI,
+            News::West  => PI * 1.5,
+        }
+    }
+}
+
+//IVec2 = IVec2 + News
+impl Add<News> for IVec2
+{   type Output = IVec2;
+    fn add( mut self, news: News ) -> IVec2
+    {   match news
+        {   News::North => { self.y -= 1; }
+            News::East  => { self.x += 1; }
+            News::West  => { self.x -= 1; }
+            News::South => { self.y += 1; }
+        }
+        self
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路の部位ごとの色(sRGBのRGBA。画像と3Dモデルの書き出しに使う)
+#[derive( Clone, Copy, PartialEq, Eq, Debug )]
+pub struct Palette
+{   pub wall     : [ u8; 4 ], //壁
+    pub wall_zero: [ u8; 4 ], //原点の壁
+    pub floor    : [ u8; 4 ], //床
+    pub goal     : [ u8; 4 ], //ゴール
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -15,6 +15,7 @@ impl Plugin for Schedule
         (   OnEnter ( MyState::InitApp ),
             (   spawn_screen_frame, //ゲーム枠を表示
                 spawn_footer,       //フッターを表示
+                spawn_side_panel,   //サイドパネルの情報表示
                 set_viewport,       //cameraにviewportを設定
             )
         )
@@ -42,20 +43,33 @@ type MessageSect<'a> =
     Color,   //フォントの色
 );
 
-//フッター(FPS表示)のComponent
+//フッター左側(FPS等の表示)のComponent
 #[derive( Component )]
-struct FooterUiFps;
+pub struct FooterUiLeft;
 
 //フッターの設定
 const NA3_2: &str = "###.##";
+const NA_STR: &str = "----";
 
 counted_array!
 (   const TEXT_FOOTER_LEFT: [ MessageSect; _ ] =
-    [   ( " FPS ", ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.6, Color::TEAL   ),
-        ( NA3_2  , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+    [   ( " FPS "  , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.6, Color::TEAL   ),
+        ( NA3_2    , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( " MAZE " , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.6, Color::TEAL   ),
+        ( NA_STR   , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( " BRAID ", ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.6, Color::TEAL   ),
+        ( NA_STR   , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( " SEED " , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.6, Color::TEAL   ),
+        ( NA_STR   , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
     ]
 );
 
+//フッター左側の各値のsectionの添字
+const FOOTER_LEFT_FPS: usize = 1;
+pub const FOOTER_LEFT_GENERATOR: usize = 3;
+pub const FOOTER_LEFT_BRAID    : usize = 5;
+pub const FOOTER_LEFT_SEED     : usize = 7;
+
 counted_array!
 (   const TEXT_FOOTER_CENTER: [ MessageSect; _ ] =
     [   ( "hyoi 2023 - xxxx", ASSETS_FONT_ORBITRON_BLACK, PIXELS_PER_GRID * 0.6, Color::TEAL ),
@@ -71,6 +85,39 @@ counted_array!
     ]
 );
 
+//サイドパネル(迷路の情報表示)のComponent
+#[derive( Component )]
+pub struct SidePanelUi;
+
+//サイドパネルの設定
+counted_array!
+(   const TEXT_SIDE_PANEL: [ MessageSect; _ ] =
+    [   ( "SEED\n"       , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.5, Color::TEAL   ),
+        ( NA_STR         , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( "\nLEVEL\n"    , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.5, Color::TEAL   ),
+        ( NA_STR         , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( "\nSIZE\n"     , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.5, Color::TEAL   ),
+        ( NA_STR         , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( "\nMAZE\n"     , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.5, Color::TEAL   ),
+        ( NA_STR         , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( "\nDEAD ENDS\n", ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.5, Color::TEAL   ),
+        ( NA_STR         , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( "\nSOLUTION\n" , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.5, Color::TEAL   ),
+        ( NA_STR         , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+        ( "\nTIME\n"     , ASSETS_FONT_ORBITRON_BLACK      , PIXELS_PER_GRID * 0.5, Color::TEAL   ),
+        ( NA_STR         , ASSETS_FONT_PRESSSTART2P_REGULAR, PIXELS_PER_GRID * 0.4, Color::SILVER ),
+    ]
+);
+
+//サイドパネルの各値のsectionの添字
+pub const SIDE_PANEL_SEED     : usize = 1;
+pub const SIDE_PANEL_LEVEL    : usize = 3;
+pub const SIDE_PANEL_SIZE     : usize = 5;
+pub const SIDE_PANEL_GENERATOR: usize = 7;
+pub const SIDE_PANEL_DEAD_ENDS: usize = 9;
+pub const SIDE_PANEL_SOLUTION : usize = 11;
+pub const SIDE_PANEL_TIME     : usize = 13;
+
 //おまけ(蟹)
 const GRID_X_KANI: i32 = SCREEN_GRIDS_WIDTH  - 4;
 const GRID_Y_KANI: i32 = SCREEN_GRIDS_HEIGHT - 1;
@@ -151,7 +198,7 @@ fn spawn_footer
     //隠しフレームの中に子要素を作成する
     cmds.spawn( hidden_frame ).with_children
     (   | cmds |
-        {   cmds.spawn( ( footer_left, FooterUiFps ) );
+        {   cmds.spawn( ( footer_left, FooterUiLeft ) );
             cmds.spawn(   footer_center              );
             cmds.spawn(   footer_right               );
         }
@@ -171,6 +218,20 @@ fn spawn_footer
     ;
 }
 
+//サイドパネルの情報表示を作る(表示エリアの左上から左寄せで並べる)
+fn spawn_side_panel
+(   mut cmds: Commands,
+    asset_svr: Res<AssetServer>,
+)
+{   let mut side_panel = text_ui( &TEXT_SIDE_PANEL, &asset_svr );
+    let zero = SIDE_PANEL_GRID_ZERO.as_vec2() * PIXELS_PER_GRID;
+    side_panel.text.alignment = TextAlignment::Left;
+    side_panel.style.left = Val::Px( zero.x );
+    side_panel.style.top  = Val::Px( zero.y );
+
+    cmds.spawn( ( side_panel, SidePanelUi ) );
+}
+
 //TextBundleを作る
 fn text_ui
 (   message: &[ MessageSect ],
@@ -198,7 +259,7 @@ fn text_ui
 
 //フッターを更新する(FPS)
 fn update_fps
-(   mut q_text: Query<&mut Text, With<FooterUiFps>>,
+(   mut q_text: Query<&mut Text, With<FooterUiLeft>>,
     diag_store: Res<DiagnosticsStore>,
 )
 {   let Ok( mut text ) = q_text.get_single_mut() else { return };
@@ -218,7 +279,7 @@ fn update_fps
         )
     );
 
-    text.sections[ 1 ].value = fps_avr;
+    text.sections[ FOOTER_LEFT_FPS ].value = fps_avr;
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -33,9 +33,18 @@ impl Plugin for Schedule
 
 ////////////////////////////////////////////////////////////////////////////////
 
-//ロードしたAssetsのハンドルの保存先
+//ロードしたAssetsのハンドルの保存先(ファイル名とハンドル)
 #[derive( Resource )]
-struct LoadedAssets { handles: Vec<HandleUntyped> }
+pub struct LoadedAssets { handles: Vec<( &'static str, HandleUntyped )> }
+
+impl LoadedAssets
+{   //事前ロードした画像のハンドルを取り出す(事前ロード対象でなければNone)
+    pub fn image( &self, fname: &str ) -> Option<Handle<Image>>
+    {   self.handles.iter()
+        .find( | ( name, _ ) | *name == fname )
+        .map( | ( _, handle ) | handle.clone().typed::<Image>() )
+    }
+}
 
 //スプライトのComponent
 #[derive( Component )]
@@ -87,7 +96,7 @@ fn start_loading
 {   //Assetsのロードを開始
     let mut handles = Vec::new();
     PRELOAD_ASSETS.iter()
-    .for_each( | fname | handles.push( asset_svr.load_untyped( *fname ) ) );
+    .for_each( | fname | handles.push( ( *fname, asset_svr.load_untyped( *fname ) ) ) );
 
     //解放しないようリソースに登録する
     cmds.insert_resource( LoadedAssets { handles } );
@@ -101,7 +110,7 @@ fn is_loading_done
     asset_svr: Res<AssetServer>,
 )
 {   //事前ロードが完了したか？
-    for handle in assets.handles.iter()
+    for ( _, handle ) in assets.handles.iter()
     {   match asset_svr.get_load_state( handle )
         {   LoadState::Loaded => (), //ロード完了
             LoadState::Failed =>
@@ -15,7 +15,7 @@ use counted_array::counted_array;
 use rand::prelude::*;
 
 //standard library
-use std::ops::{ Range, Add };
+use std::ops::{ Range, RangeInclusive };
 use std::f32::consts::{ PI, TAU };
 
 //internal submodules
@@ -30,7 +30,10 @@ mod play_game;
 
 //メイン関数
 fn main()
-{   //アプリの生成
+{   //ウィンドウを開かずに終了するコマンド
+    if play_game::run_headless() { return }
+
+    //アプリの生成
     let mut app = App::new();
 
     //メインウィンドウの設定
@@ -47,7 +50,8 @@ fn main()
     )
     .add_systems
     (   Startup,
-        (   misc::spawn_2d_camera, //2D camera
+        (   misc::warn_cmdline_errors, //コマンドライン引数のエラー
+            misc::spawn_2d_camera, //2D camera
             misc::spawn_3d_camera, //3D camera
             misc::spawn_3d_light,  //3D light
             debug::spawn_2d_sprites.run_if( misc::DEBUG ), //2D表示テスト
@@ -1,6 +1,15 @@
 use super::*;
 
 mod map;
+mod player;
+mod first_person;
+mod theme;
+mod minimap;
+mod hud;
+
+//ウィンドウを開かないコマンド
+mod headless;
+pub use headless::run_headless;
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -14,15 +23,91 @@ impl Plugin for Schedule
 
         //Resourceの登録
         .init_resource::<map::Map>() //Map情報
+        .init_resource::<map::Digging>() //迷路を一歩ずつ作る状態
+        .init_resource::<first_person::FirstPerson>() //一人称視点の状態
+        .init_resource::<theme::Themes>() //テーマの一覧
+        .init_resource::<theme::Theme>()  //迷路の見た目(Themesの後に登録する)
+        .init_resource::<hud::ElapsedTime>() //経過時間
 
         //前処理
         .add_systems
         (   OnEnter ( MyState::GameStart ),
-            (   map::make_new_data, //新しいMapデータを作る
-                map::init_orbit_camera::<misc::AppDefault3dCamera>, //カメラを初期化
-                map::spawn_entity,  //Mapを3D表示する
+            (   map::make_new_data,     //新しいMapデータを作る
+                player::spawn_player,   //自キャラをspawnする
+                minimap::spawn_minimap, //ミニマップをspawnする
+            )
+            .chain()
+        )
+
+        //メインループ
+        .add_systems
+        (   Update,
+            (   (   map::regenerate,        //迷路の作り直し
+                    map::change_generator,  //迷路作成アルゴリズムの切換
+                    map::change_braid,      //行き止り解消の割合の切換
+                    map::change_size,       //マップの縦横幅の切換
+                    map::change_difficulty, //難しさの切換
+                )
+                .chain(),
+                map::save_file,        //迷路ファイルの保存
+                map::export_model,     //3Dモデルの書き出し
+                theme::change_theme,   //テーマの切換
+                theme::restyle::<misc::AppDefault3dCamera> //見た目だけ変える
+                    .run_if( resource_changed::<theme::Theme>() ),
+                map::toggle_dig,       //迷路を一歩ずつ作る
+                (   map::control_dig,  //一時停止・一歩だけ進める・速さの切換
+                    map::advance_dig,  //アルゴリズムを進めて変わったチャンクを作り直す
+                )
+                .run_if( map::is_digging ),
+                map::update_dig_head,  //掘っている先頭の目印
+                (   map::init_orbit_camera::<misc::AppDefault3dCamera>, //カメラを初期化
+                    map::spawn_entity,    //Mapを3D表示する
+                    map::update_footer,   //フッターを更新する
+                    hud::update_side_panel, //サイドパネルを更新する
+                    player::reset_player, //自キャラをスタート地点へ戻す
+                    first_person::reset_facing, //一人称視点の向きを戻す
+                )
+                .run_if( resource_changed::<map::Map>() ), //Mapが変化した時だけ
+                minimap::update_image //ミニマップを描き直す
+                    .run_if
+                    (   resource_changed::<map::Map>()
+                        .or_else( resource_changed::<theme::Theme>() )
+                        .or_else( map::is_dug ) //一歩ずつ作る途中もMapの変化を隠して掘る
+                    ),
+
+                //自キャラの操作
+                first_person::toggle_view, //俯瞰と一人称の切換
+                (   player::catch_input_direction,   //キー入力・ゲームパッド
+                    player::catch_input_mouse_click, //マウスの右クリック
+                )
+                .run_if( first_person::is_orbit_view )
+                .run_if( not( map::is_digging ) ),
+                first_person::catch_input_walk //旋回と前後の移動
+                    .run_if( first_person::is_first_person )
+                    .run_if( not( map::is_digging ) ),
+                player::move_player,         //移動
+                hud::update_elapsed_time,    //経過時間
+                first_person::update_blend,  //旋回と視点切換
+                player::follow_player        //カメラの注視点を合わせる
+                    .before( misc::move_orbit_camera::<misc::AppDefault3dCamera> ),
             )
             .chain()
+            .run_if( in_state( MyState::GameStart ) )
+        )
+        .add_systems
+        (   Update,
+            (   player::face_camera,       //ビルボードの向きを合わせる
+                first_person::move_camera::<misc::AppDefault3dCamera>, //一人称視点のカメラ
+                first_person::hide_player, //一人称視点では自キャラを隠す
+            )
+            .after( misc::move_orbit_camera::<misc::AppDefault3dCamera> ) //俯瞰カメラの移動後
+            .run_if( in_state( MyState::GameStart ) )
+        )
+        .add_systems
+        (   Update,
+            minimap::update_player::<misc::AppDefault3dCamera> //ミニマップの自キャラとカメラの向き
+                .after( first_person::move_camera::<misc::AppDefault3dCamera> ) //カメラの移動後
+                .run_if( in_state( MyState::GameStart ) )
         )
         ;
     }
@@ -0,0 +1,174 @@
+use super::*;
+use map::Map;
+use player::Player;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//一人称視点のResource
+#[derive( Resource, Default )]
+pub struct FirstPerson
+{   enabled: bool, //一人称視点か？(falseなら俯瞰の極座標カメラ)
+    blend  : f32,  //視点切換の進み具合(0.0:俯瞰 ～ 1.0:一人称)
+    facing : News, //向いている方向
+    yaw    : f32,  //カメラの水平角度(ラジアン、facingへ向けて補間する)
+}
+
+//一人称視点の設定
+const FP_EYE_HEIGHT  : f32 = 0.55;     //地面からの目の高さ
+const FP_TURN_SPEED  : f32 = PI * 2.0; //1秒あたりの旋回角度(ラジアン)
+const FP_BLEND_SPEED : f32 = 1.5;      //1秒あたりの視点切換の進み具合
+
+//.run_if( condition )用
+pub fn is_orbit_view( fp: Res<FirstPerson> ) -> bool { ! fp.enabled }
+pub fn is_first_person( fp: Res<FirstPerson> ) -> bool { fp.enabled }
+
+////////////////////////////////////////////////////////////////////////////////
+
+//俯瞰と一人称の視点を切り換える(トグル動作)
+pub fn toggle_view
+(   mut fp: ResMut<FirstPerson>,
+    o_camera: Option<Res<OrbitCamera>>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //[V]キーの状態
+    let is_key_pressed = inkey.just_pressed( KeyCode::V );
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let button_type = GamepadButtonType::LeftThumb; //ps4[L3]
+    let is_gpdbtn_pressed = gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //入力がないなら
+    if ! is_key_pressed && ! is_gpdbtn_pressed { return }
+
+    fp.enabled = ! fp.enabled;
+    if ! fp.enabled { return }
+
+    //一人称にする時は、俯瞰カメラの向きに近い方向を向く
+    let phi = o_camera.map_or( ORBIT_CAMERA_INIT_PHI, | camera | camera.orbit.phi );
+    fp.facing = player::nearest_news( Vec2::new( -phi.sin(), -phi.cos() ) );
+    fp.yaw = fp.facing.yaw();
+}
+
+//Mapが作り直されたら、スタート地点で空地の方を向く
+pub fn reset_facing
+(   mut fp: ResMut<FirstPerson>,
+    map: Res<Map>,
+)
+{   let start = map.start();
+    fp.facing = NEWS.into_iter()
+        .find( | &news | map.is_space( start + news ) )
+        .unwrap_or_default();
+    fp.yaw = fp.facing.yaw();
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//キー入力・ゲームパッドで旋回と前後の移動をする
+pub fn catch_input_walk
+(   mut q_player: Query<&mut Player>,
+    mut fp: ResMut<FirstPerson>,
+    map: Res<Map>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   let Ok ( mut player ) = q_player.get_single_mut() else { return };
+
+    //移動中・旋回中は受け付けない
+    if player.is_moving() || fp.yaw != fp.facing.yaw() { return }
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let is_gpdbtn_pressed = | button_type | gamepads.iter().any
+    (   | gamepad | gpdbtn.pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //旋回
+    if inkey.pressed( KeyCode::A ) || is_gpdbtn_pressed( GamepadButtonType::DPadLeft )
+    {   fp.facing = fp.facing.turn_left();
+        return;
+    }
+    if inkey.pressed( KeyCode::D ) || is_gpdbtn_pressed( GamepadButtonType::DPadRight )
+    {   fp.facing = fp.facing.turn_right();
+        return;
+    }
+
+    //前進と後退(壁には入れない)
+    let news = if inkey.pressed( KeyCode::W ) || is_gpdbtn_pressed( GamepadButtonType::DPadUp )
+    {   fp.facing
+    }
+    else if inkey.pressed( KeyCode::S ) || is_gpdbtn_pressed( GamepadButtonType::DPadDown )
+    {   fp.facing.reverse()
+    }
+    else
+    {   return
+    };
+    let next = player.grid() + news;
+    if map.is_wall( next ) { return }
+    player.step_to( next );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//旋回と視点切換を進める
+pub fn update_blend
+(   mut fp: ResMut<FirstPerson>,
+    time: Res<Time>,
+)
+{   let time_delta = time.delta().as_secs_f32(); //前回の実行からの経過時間
+
+    //向きをfacingへ近づける(近い方へ回る)
+    let diff = ( fp.facing.yaw() - fp.yaw + PI ).rem_euclid( TAU ) - PI;
+    let step = FP_TURN_SPEED * time_delta;
+    fp.yaw = if diff.abs() <= step
+    {   fp.facing.yaw()
+    }
+    else
+    {   ( fp.yaw + step * diff.signum() ).rem_euclid( TAU )
+    };
+
+    //視点切換を進める
+    let target = if fp.enabled { 1.0 } else { 0.0 };
+    let step = FP_BLEND_SPEED * time_delta;
+    fp.blend += ( target - fp.blend ).clamp( -step, step );
+}
+
+//一人称視点のカメラを動かす
+//＜注意＞ 俯瞰カメラの位置と向きが決まった後に実行し、視点切換の途中は両者を補間する
+pub fn move_camera<T: Component>
+(   mut q_camera: Query<&mut Transform, With<T>>,
+    q_player: Query<&Player>,
+    fp: Res<FirstPerson>,
+)
+{   if fp.blend <= 0.0 { return }
+    let Ok ( mut transform ) = q_camera.get_single_mut() else { return };
+    let Ok ( player ) = q_player.get_single() else { return };
+
+    //目の位置と視線の方向
+    let eye = player.floor_position() + Vec3::Y * ( FP_EYE_HEIGHT - 0.5 );
+    let dir = Vec3::new( fp.yaw.sin(), 0.0, -fp.yaw.cos() );
+    let first_person = Transform::from_translation( eye ).looking_at( eye + dir, Vec3::Y );
+
+    //滑らかに補間する(smoothstep)
+    let t = fp.blend * fp.blend * ( 3.0 - 2.0 * fp.blend );
+    transform.translation = transform.translation.lerp( first_person.translation, t );
+    transform.rotation    = transform.rotation.slerp( first_person.rotation, t );
+}
+
+//一人称視点の間は自キャラを隠す
+pub fn hide_player
+(   mut q_player: Query<&mut Visibility, With<Player>>,
+    fp: Res<FirstPerson>,
+)
+{   let Ok ( mut visibility ) = q_player.get_single_mut() else { return };
+
+    let is_hidden = fp.blend > 0.5;
+    *visibility = if is_hidden { Visibility::Hidden } else { Visibility::Inherited };
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
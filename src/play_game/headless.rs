@@ -0,0 +1,122 @@
+use super::*;
+use map::{ Map, MapFormat };
+
+////////////////////////////////////////////////////////////////////////////////
+
+//ウィンドウを開かずに処理するコマンドが指定されているか？
+fn is_headless() -> bool
+{   CMDLINE.export  .is_some() ||
+    CMDLINE.render  .is_some() ||
+    CMDLINE.output  .is_some() ||
+    CMDLINE.validate.is_some() ||
+    CMDLINE.print ||
+    CMDLINE.stats
+}
+
+//エラーなら表示して終了する
+fn or_exit<T>( name: &str, result: Result<T, String> ) -> T
+{   result.unwrap_or_else
+    (   | err |
+        {   eprintln!( "{name}: {err}" );
+            std::process::exit( 1 );
+        }
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//コマンドラインで指定された場合、ウィンドウを開かずに処理して終了する
+//（処理した場合はtrueを返す。迷路のデータは標準出力へ、経過は標準エラー出力へ書く）
+pub fn run_headless() -> bool
+{   if ! is_headless() { return false }
+    CMDLINE.errors.iter().for_each( | err | eprintln!( "{err}" ) );
+
+    //迷路ファイルを検査する(迷路は作らない)
+    if let Some ( fname ) = &CMDLINE.validate
+    {   validate_file( fname );
+        return true
+    }
+
+    //アルゴリズムの名前を確かめる
+    if let Some ( name ) = &CMDLINE.algorithm
+    {   let names = map::generator_names().join( ", " );
+        let result = map::find_generator( name )
+            .ok_or_else( || format!( "Unknown algorithm \"{name}\" (one of {names})" ) );
+        or_exit( "--algorithm", result );
+    }
+
+    //難しさの名前を確かめる
+    let level = CMDLINE.difficulty.as_ref().map
+    (   | name |
+        {   let result = map::Difficulty::find( name )
+                .ok_or_else( || format!( "Unknown difficulty \"{name}\" (one of EASY, MEDIUM, HARD)" ) );
+            or_exit( "--difficulty", result )
+        }
+    );
+
+    //迷路を用意する(読込に失敗したら終了する。難しさを指定したら選んだシードと設定を表示する)
+    let map = match ( &CMDLINE.load, level )
+    {   ( Some ( fname ), _ ) => or_exit( fname, Map::load( fname ) ),
+        ( None, Some ( level ) ) =>
+        {   let mut map = Map::default();
+            eprintln!( "{}", map.generate_level( level ).unwrap_or_else( | err | err ) );
+            map
+        }
+        ( None, None ) =>
+        {   let mut map = Map::default();
+            map.load_or_generate();
+            map
+        }
+    };
+    let themes = theme::Themes::default();
+
+    //ASCII形式で標準出力に書き出す
+    if CMDLINE.print { print!( "{}", map.to_ascii() ) }
+
+    //迷路ファイルを書き出す
+    if let Some ( fname ) = &CMDLINE.output
+    {   let format = MapFormat::from_fname( fname );
+        or_exit( fname, map.serialize( format ).and_then( | text | misc::write_file( fname, text ) ) );
+        eprintln!( "Saved maze file \"{fname}\" (seed {})", map.seed() );
+    }
+
+    //3Dモデルを書き出す
+    if let Some ( fname ) = &CMDLINE.export
+    {   or_exit( fname, map.write_obj( themes.current(), fname ) );
+        eprintln!( "Exported 3D model \"{fname}\" (seed {})", map.seed() );
+    }
+
+    //画像を書き出す
+    if let Some ( fname ) = &CMDLINE.render
+    {   let options = map::RenderOptions
+        {   solution : CMDLINE.solution,
+            dead_ends: CMDLINE.dead_ends,
+            ..default()
+        };
+        or_exit( fname, map.write_image( themes.current(), options, fname ) );
+        eprintln!( "Rendered image \"{fname}\" (seed {})", map.seed() );
+    }
+
+    //統計を表示する
+    if CMDLINE.stats { print!( "{}", map.stats() ) }
+
+    true
+}
+
+//迷路ファイルを検査して統計を表示する(問題があれば終了コード1で終了する)
+fn validate_file( fname: &str )
+{   let map = or_exit( fname, Map::load( fname ) );
+    print!( "{}", map.stats() );
+
+    let problems = map.validate();
+    if problems.is_empty()
+    {   println!( "{fname}: OK" );
+        return
+    }
+    problems.iter().for_each( | problem | println!( "{fname}: {problem}" ) );
+    std::process::exit( 1 );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
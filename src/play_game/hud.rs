@@ -0,0 +1,66 @@
+use super::*;
+use map::{ Map, Difficulty };
+use player::Player;
+use bevy::time::Stopwatch;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路を作ってからの経過時間(ゴールに着いたら止める)
+#[derive( Resource, Default )]
+pub struct ElapsedTime { stopwatch: Stopwatch }
+
+////////////////////////////////////////////////////////////////////////////////
+
+//サイドパネルを更新する(シード、難しさ、縦横幅、アルゴリズム、行き止りの数、解の歩数)
+//＜注意＞ Mapが変化した時だけ実行し、経過時間もリセットする
+pub fn update_side_panel
+(   mut q_text: Query<&mut Text, With<init_app::SidePanelUi>>,
+    mut elapsed: ResMut<ElapsedTime>,
+    map: Res<Map>,
+)
+{   elapsed.stopwatch.reset();
+    elapsed.stopwatch.unpause();
+
+    let Ok( mut text ) = q_text.get_single_mut() else { return };
+
+    //難しさは段階と点数、解は歩数と経路上のマスの割合
+    let stats = map.stats();
+    let level = stats.difficulty().map_or( "----".to_string(), | score | format!( "{} {score:.0}", Difficulty::from_score( score ) ) );
+    let solution = stats.solution.zip( stats.on_path ).map_or
+    (   "----".to_string(),
+        | ( steps, on_path ) | format!( "{steps} ({:.0}%)", on_path * 100.0 )
+    );
+
+    let sections = &mut text.sections;
+    sections[ init_app::SIDE_PANEL_SEED      ].value = map.seed().to_string();
+    sections[ init_app::SIDE_PANEL_LEVEL     ].value = level;
+    sections[ init_app::SIDE_PANEL_SIZE      ].value = format!( "{}x{}", map.width(), map.height() );
+    sections[ init_app::SIDE_PANEL_GENERATOR ].value = map.generator_name().to_string();
+    sections[ init_app::SIDE_PANEL_DEAD_ENDS ].value = stats.dead_ends.to_string();
+    sections[ init_app::SIDE_PANEL_SOLUTION  ].value = solution;
+}
+
+//経過時間を進めて表示する
+pub fn update_elapsed_time
+(   mut q_text: Query<&mut Text, With<init_app::SidePanelUi>>,
+    q_player: Query<&Player>,
+    mut elapsed: ResMut<ElapsedTime>,
+    map: Res<Map>,
+    time: Res<Time>,
+)
+{   //自キャラがゴールに着いたら止める
+    if let Ok ( player ) = q_player.get_single()
+    {   if player.grid() == map.goal() && ! player.is_moving() { elapsed.stopwatch.pause() }
+    }
+    elapsed.stopwatch.tick( time.delta() );
+
+    let Ok( mut text ) = q_text.get_single_mut() else { return };
+
+    let secs = elapsed.stopwatch.elapsed_secs();
+    let value = format!( "{:02}:{:05.2}", secs as u32 / 60, secs % 60.0 );
+    text.sections[ init_app::SIDE_PANEL_TIME ].value = value;
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
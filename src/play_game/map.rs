@@ -1,195 +1,294 @@
 use super::*;
+use chip12_maze as maze;
+pub use maze::{ MapFormat, RenderOptions, Difficulty, find_generator, generator_names };
+
+//3D表示用のMesh
+mod mesh;
+
+//ファイルへの書き出し
+mod export;
+
+//迷路を一歩ずつ作る様子を見せる
+mod dig;
+pub use dig::*;
 
 ////////////////////////////////////////////////////////////////////////////////
 
-//MapのResource
-#[derive( Resource )]
-pub struct Map
-{   rng   : rand::prelude::StdRng, //専用乱数発生器
-    matrix: Vec<Vec<Flag>>,        //map
-    start : IVec2,                 //スタート位置
-}
+//MapのResource(迷路の本体はchip12-mazeクレートのMap)
+#[derive( Resource, Deref, DerefMut )]
+pub struct Map ( maze::Map );
 
-//マスの情報
-#[derive( Clone )]
-struct Flag ( u128 );
+//新しいシードを作る(報告しやすいようにu32の範囲にする)
+fn new_seed() -> u64
+{   rand::thread_rng().gen::<u32>() as u64
+}
 
 //Map::default()の定義
 impl Default for Map
 {   fn default() -> Self
-    {   let seed_dev = 1234567890;
-        let seed_rel = || rand::thread_rng().gen::<u64>();
-        let seed = if misc::DEBUG() { seed_dev } else { seed_rel() };
-
-        let cell = Flag ( BIT_CELL_UNDEF );
-        let column = vec![ cell  ; MAP_GRIDS_HEIGHT as usize ];
-        let matrix = vec![ column; MAP_GRIDS_WIDTH  as usize ];
-
-        Self
-        {   rng  : StdRng::seed_from_u64( seed ),
-            matrix,
-            start: IVec2::default(),
+    {   //シードはコマンドライン引数で指定できる
+        let seed_dev = 1234567890;
+        let seed = CMDLINE.seed.unwrap_or_else
+        (   || if misc::DEBUG() { seed_dev } else { new_seed() }
+        );
+
+        //縦横幅はコマンドライン引数で指定できる
+        let width  = CMDLINE.width .unwrap_or( MAP_GRIDS_WIDTH  );
+        let height = CMDLINE.height.unwrap_or( MAP_GRIDS_HEIGHT );
+
+        let mut map = maze::Map::new( width, height, seed );
+        map.set_goal_min_distance( CMDLINE.goal_min.unwrap_or( MAP_GOAL_MIN_DISTANCE ) );
+        map.set_braid_percent( MAP_BRAID_PERCENTS[ 0 ] );
+
+        //アルゴリズムはコマンドライン引数で指定できる
+        if let Some ( name ) = &CMDLINE.algorithm
+        {   match find_generator( name )
+            {   Some ( i ) => map.set_generator( i ),
+                None => warn!( "Unknown algorithm \"{name}\"" ),
+            }
         }
-    }
-}
 
-//マス目の状態を表すビット(フラグは128個まで)
-const BIT_CELL_UNDEF  : u128 = 0b000; //未定義
-const BIT_CELL_SPACE  : u128 = 0b001; //地形：空地
-const BIT_CELL_WALL   : u128 = 0b010; //地形：壁
-const BIT_FLAG_DEADEND: u128 = 0b100; //フラグ：行き止り
+        //ダンジョンの部屋の数と縦横幅もコマンドライン引数で指定できる
+        if let Some ( range ) = &CMDLINE.rooms     { map.set_room_count( range.clone() ) }
+        if let Some ( range ) = &CMDLINE.room_size { map.set_room_size ( range.clone() ) }
 
-////////////////////////////////////////////////////////////////////////////////
+        Self ( map )
+    }
+}
 
-//Mapのメソッド
 impl Map
-{   //ユーティリティ
-    fn is_inside( &self, cell: IVec2 ) -> bool
-    {   MAP_GRIDS_X_RANGE.contains( &cell.x ) &&
-        MAP_GRIDS_Y_RANGE.contains( &cell.y )
-    }
-    fn matrix_mut( &mut self, IVec2 { x, y }: IVec2 ) -> &mut Flag
-    {   &mut self.matrix[ x as usize ][ y as usize ]
-    }
-    fn matrix( &self, IVec2 { x, y }: IVec2 ) -> &Flag
-    {   &self.matrix[ x as usize ][ y as usize ]
+{   //迷路を作り直す(ゴールが最低歩数より近い場合は警告する)
+    fn generate_or_warn( &mut self )
+    {   let is_goal_ok = self.generate();
+        self.warn_goal( is_goal_ok );
     }
 
-    //全体を埋める
-    fn fill_walls( &mut self )
-    {   self.matrix.iter_mut().for_each
-        (   |column| column.fill( Flag ( BIT_CELL_WALL ) )
+    //ゴールが最低歩数より近い場合は警告する
+    fn warn_goal( &self, is_goal_ok: bool )
+    {   if is_goal_ok { return }
+        warn!
+        (   "Goal is closer than {} steps (seed {}, {})",
+            self.goal_min_distance(), self.seed(), self.generator_name()
         );
     }
 
-    //指定の位置の地形を書き換える（フラグはクリアされる）
-    fn set_space( &mut self, cell: IVec2 )
-    {   if ! self.is_inside( cell ) { return }
-        *self.matrix_mut( cell ) = Flag ( BIT_CELL_SPACE );
-    }
+    //指定の難しさで迷路を作り直し、選んだシードと設定を報告する文を返す
+    //（--tuneの時だけアルゴリズムとループ化の割合も変え、変えた場合は元の設定も報告する）
+    pub fn generate_level( &mut self, level: Difficulty ) -> Result<String, String>
+    {   let is_found =
+            if CMDLINE.tune { self.generate_with_difficulty_tuned( level ) }
+            else            { self.generate_with_difficulty      ( level ) };
 
-    //指定の位置の地形にフラグを付加する
-    fn add_flag_deadend( &mut self, cell: IVec2 )
-    {   if ! self.is_inside( cell ) { return }
-        self.matrix_mut( cell ).0 |= BIT_FLAG_DEADEND;
-    }
+        let mut report = format!
+        (   "seed {}, {}, braid {}%",
+            self.seed(), self.generator_name(), self.braid_percent()
+        );
+        if let Some ( ( generator, braid_percent ) ) = self.tuned_from()
+        {   let name = maze::GENERATORS[ generator ].name();
+            report += &format!( ", tuned from {name}, braid {braid_percent}%" );
+        }
 
-    //指定の位置の地形・フラグを判定する
-    fn is_wall( &self, cell: IVec2 ) -> bool
-    {   if ! self.is_inside( cell ) { return true } //範囲外は壁にする
-        self.matrix( cell ).0 & BIT_CELL_WALL != 0
+        if is_found { Ok ( format!( "{level} maze ({report})" ) ) }
+        else        { Err ( format!( "No {level} maze was found ({report})" ) ) }
     }
-    fn is_space( &self, cell: IVec2 ) -> bool
-    {   if ! self.is_inside( cell ) { return false } //範囲外に空地はない
-        self.matrix( cell ).0 & BIT_CELL_SPACE != 0
-    }
-    fn is_deadend( &self, cell: IVec2 ) -> bool
-    {   if ! self.is_inside( cell ) { return false } //範囲外に空地はない(＝行き止りもない)
-        self.matrix( cell ).0 & BIT_FLAG_DEADEND != 0
+
+    //指定の難しさで迷路を作り直す(見つからなかった場合は警告する)
+    fn generate_level_or_warn( &mut self, level: Difficulty )
+    {   match self.generate_level( level )
+        {   Ok  ( report ) => info!( "{report}" ),
+            Err ( report ) => warn!( "{report}" ),
+        }
     }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
 
-//Mapのメソッド（迷路作成）
+//新しいMapデータを作る
+pub fn make_new_data( mut map: ResMut<Map> )
+{   map.load_or_generate();
+}
+
 impl Map
-{   //迷路作成メソッド
-    fn build_labyrinth( &mut self )
-    {   //穴を掘る準備
-        let mut cell = self.start;
-        let mut digable_walls = Vec::new();
-        let mut backtrack;
-
-        //穴掘りループ
-        loop
-        {   //四方の判定準備
-            digable_walls.clear();
-            backtrack = IVec2::NEG_ONE;
-
-            //四方の掘れる壁と戻り道を記録する
-            for news in NEWS
-            {   let next = cell + news;
-
-                //外壁は掘れない
-                if ! MAP_GRIDS_X_RANGE_INNER.contains( &next.x )
-                || ! MAP_GRIDS_Y_RANGE_INNER.contains( &next.y ) { continue }
-
-                //四方のグリッドを調べる
-                if self.is_wall( next ) && self.is_digable( next, news )
-                {   //壁であり且つ掘れるなら
-                    digable_walls.push( next );
-                }
-                else if self.is_space( next ) && ! self.is_deadend( next )
-                {   //道であり且つ行止りのマーキングがないなら
-                    backtrack = next;
-                }
-            }
+{   //迷路ファイルを読み込む(形式は拡張子で決める)
+    pub fn load( fname: &str ) -> Result<Map, String>
+    {   let format = MapFormat::from_fname( fname );
+        misc::read_file( fname ).and_then( | text | maze::Map::deserialize( &text, format ) ).map( Map )
+    }
 
-            if ! digable_walls.is_empty()
-            {   //掘れる壁が見つかったので、方向をランダムに決めて進む
-                cell = digable_walls[ self.rng.gen_range( 0..digable_walls.len() ) ];
-                self.set_space( cell );
+    //迷路ファイルが指定されていれば読み込み、なければ迷路を作る
+    pub fn load_or_generate( &mut self )
+    {   if let Some ( fname ) = &CMDLINE.load
+        {   match Map::load( fname )
+            {   Ok ( loaded ) => { *self = loaded; return }
+                Err ( err ) => warn!( "Maze file \"{fname}\": {err}" ),
             }
-            else
-            {   //掘れる壁が見つからず、戻り道も見つからないなら迷路完成
-                if backtrack == IVec2::NEG_ONE { break }
+        }
 
-                //現在位置に行き止まりをマークし、戻り路へ進む(後戻りする)
-                self.add_flag_deadend( cell );
-                cell = backtrack;
+        //難しさはコマンドライン引数で指定できる
+        if let Some ( name ) = &CMDLINE.difficulty
+        {   match Difficulty::find( name )
+            {   Some ( level ) => { self.generate_level_or_warn( level ); return }
+                None => warn!( "Unknown difficulty \"{name}\"" ),
             }
         }
+
+        self.generate_or_warn();
     }
+}
 
-    //壁が掘れるか調べる
-    fn is_digable( &self, cell: IVec2, news: News ) -> bool
-    {    match news
-        {   News::North
-            if self.is_wall( cell + News::North + News::West )
-            && self.is_wall( cell + News::North              ) // 壁壁壁
-            && self.is_wall( cell + News::North + News::East ) // 壁？壁
-            && self.is_wall( cell + News::West               )
-                => true,
-            News::West
-            if self.is_wall( cell + News::North + News::West )
-            && self.is_wall( cell + News::North              ) // 壁壁
-            && self.is_wall( cell + News::West               ) // 壁？◎
-            && self.is_wall( cell + News::South + News::West ) // 壁壁
-            && self.is_wall( cell + News::South              )
-                => true,
-            News::East
-            if self.is_wall( cell + News::North              )
-            && self.is_wall( cell + News::North + News::East ) // 　壁壁
-            && self.is_wall( cell + News::East               ) // ◎？壁
-            && self.is_wall( cell + News::South              ) // 　壁壁
-            && self.is_wall( cell + News::South + News::East )
-                => true,
-            News::South
-            if self.is_wall( cell + News::West               )
-            && self.is_wall( cell + News::East               ) // 　◎
-            && self.is_wall( cell + News::South + News::West ) // 壁？壁
-            && self.is_wall( cell + News::South              ) // 壁壁壁
-            && self.is_wall( cell + News::South + News::East )
-                => true,
-            _   => false,
-        }
+//迷路をファイルに保存する([F5]キーはASCII形式、[F6]キーはRON形式)
+pub fn save_file
+(   map: Res<Map>,
+    inkey: Res<Input<KeyCode>>,
+)
+{   let format =
+        if      inkey.just_pressed( KeyCode::F5 ) { MapFormat::Ascii }
+        else if inkey.just_pressed( KeyCode::F6 ) { MapFormat::Ron   }
+        else { return };
+
+    let fname = format!( "maze-{}.{}", map.seed(), format.extension() );
+    match map.serialize( format ).and_then( | text | misc::write_file( &fname, &text ) )
+    {   Ok ( () ) => info!( "Saved maze file \"{fname}\"" ),
+        Err ( err ) => warn!( "Maze file \"{fname}\": {err}" ),
     }
 }
 
-////////////////////////////////////////////////////////////////////////////////
+//壁と床の3DモデルをOBJ形式で書き出す([F7]キー)
+pub fn export_model
+(   map: Res<Map>,
+    theme: Res<theme::Theme>,
+    inkey: Res<Input<KeyCode>>,
+)
+{   if ! inkey.just_pressed( KeyCode::F7 ) { return }
 
-//新しいMapデータを作る
-pub fn make_new_data( mut map: ResMut<Map> )
-{   //初期化する
-    map.fill_walls();
+    let fname = format!( "maze-{}.obj", map.seed() );
+    match map.write_obj( &theme, &fname )
+    {   Ok ( () ) => info!( "Exported 3D model \"{fname}\"" ),
+        Err ( err ) => warn!( "3D model \"{fname}\": {err}" ),
+    }
+}
 
-    //スタート地点を決める
-    map.start = IVec2::new( MAP_GRIDS_WIDTH / 2, MAP_GRIDS_HEIGHT / 2 );
-    let start = map.start;
-    map.set_space( start );
+//迷路作成アルゴリズムを切り替えて迷路を作り直す
+pub fn change_generator
+(   mut map: ResMut<Map>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //[G]キーの状態
+    let is_key_pressed = inkey.just_pressed( KeyCode::G );
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let button_type = GamepadButtonType::North; //ps4[△]
+    let is_gpdbtn_pressed = gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //入力がないなら
+    if ! is_key_pressed && ! is_gpdbtn_pressed { return }
+
+    //次のアルゴリズムで迷路を作り直す
+    let generator = map.generator() + 1;
+    map.set_generator( generator ); //一周したら先頭に戻る
+    map.generate_or_warn();
+}
+
+//行き止りを解消する割合を切り替えて迷路を作り直す
+pub fn change_braid
+(   mut map: ResMut<Map>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //[B]キーの状態
+    let is_key_pressed = inkey.just_pressed( KeyCode::B );
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let button_type = GamepadButtonType::West; //ps4[□]
+    let is_gpdbtn_pressed = gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //入力がないなら
+    if ! is_key_pressed && ! is_gpdbtn_pressed { return }
 
-    //迷路を作る
-    map.build_labyrinth();
+    //次の割合で迷路を作り直す
+    let i = MAP_BRAID_PERCENTS.iter().position( | &x | x == map.braid_percent() ).unwrap_or( 0 );
+    map.set_braid_percent( MAP_BRAID_PERCENTS[ ( i + 1 ) % MAP_BRAID_PERCENTS.len() ] );
+    map.generate_or_warn();
+}
+
+//マップの縦横幅を切り替えて迷路を作り直す
+pub fn change_size
+(   mut map: ResMut<Map>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //[N]キーの状態
+    let is_key_pressed = inkey.just_pressed( KeyCode::N );
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let button_type = GamepadButtonType::East; //ps4[○]
+    let is_gpdbtn_pressed = gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //入力がないなら
+    if ! is_key_pressed && ! is_gpdbtn_pressed { return }
+
+    //次の縦横幅で迷路を作り直す
+    let size = ( map.width(), map.height() );
+    let i = MAP_GRIDS_PRESETS.iter().position( | &x | x == size ).unwrap_or( 0 );
+    let ( width, height ) = MAP_GRIDS_PRESETS[ ( i + 1 ) % MAP_GRIDS_PRESETS.len() ];
+    map.resize( width, height );
+    map.generate_or_warn();
+}
+
+//難しさを一段階上げて(HARDの次はEASY)、新しいシードから探して迷路を作り直す
+pub fn change_difficulty
+(   mut map: ResMut<Map>,
+    inkey: Res<Input<KeyCode>>,
+)
+{   //[L]キーの状態
+    if ! inkey.just_pressed( KeyCode::L ) { return }
+
+    //今の迷路の難しさの次の段階
+    let level = match map.stats().level()
+    {   Some ( Difficulty::Easy   ) => Difficulty::Medium,
+        Some ( Difficulty::Medium ) => Difficulty::Hard,
+        _ => Difficulty::Easy,
+    };
+
+    //アルゴリズムとループ化の割合はそのまま(--tuneの時だけ変えてよい)
+    map.set_seed( new_seed() );
+    map.generate_level_or_warn( level );
+}
+
+//シードを変えて(または同じシードで)迷路を作り直す
+pub fn regenerate
+(   mut map: ResMut<Map>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //[R]キーは新しいシード、[Shift]＋[R]キーは同じシード
+    let is_shift = inkey.pressed( KeyCode::ShiftLeft ) || inkey.pressed( KeyCode::ShiftRight );
+    let is_key_pressed = inkey.just_pressed( KeyCode::R );
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let is_gpdbtn_pressed = | button_type | gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+    let is_new_seed  = ( is_key_pressed && ! is_shift ) || is_gpdbtn_pressed( GamepadButtonType::Start      ); //ps4[OPTIONS]
+    let is_same_seed = ( is_key_pressed &&   is_shift ) || is_gpdbtn_pressed( GamepadButtonType::RightThumb ); //ps4[R3]
+
+    //入力がないなら
+    if ! is_new_seed && ! is_same_seed { return }
+
+    //迷路を作り直す
+    if is_new_seed { map.set_seed( new_seed() ) }
+    map.generate_or_warn();
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -204,9 +303,11 @@ pub fn init_orbit_camera<T: Component>
 )
 {   let Ok ( mut transform ) = q_camera.get_single_mut() else { return };
 
-    //初期値を準備する
+    //初期値を準備する(rの最大値はマップの長辺に合わせる)
+    let long_side = map.width().max( map.height() ) as f32;
     let camera = OrbitCamera
-    {   look_at: map.start.to_3dxz(),
+    {   look_at: map.start().to_3dxz(),
+        max_r  : ORBIT_CAMERA_MAX_R.max( long_side * ORBIT_CAMERA_MAX_R_PER_GRID ),
         ..default()
     };
 
@@ -230,16 +331,29 @@ pub fn init_orbit_camera<T: Component>
 #[derive( Component )]
 pub struct MapZeroEntity;
 
-//mapオブジェクト関係
-const WALL_CUBE_OBJ3D_SIZE      : f32 = 0.9; //拡大率
-const WALL_CUBE_OBJ3D_COLOR     : Color = Color::BISQUE; //通常Cubeの色
-const WALL_CUBE_OBJ3D_COLOR_ZERO: Color = Color::RED;    //原点Cubeの色
-const GROUND_PLANE_OBJ3D_COLOR  : Color = Color::MAROON; //地面の色
+//壁と床のチャンクのEntityに印をつけるComponent
+#[derive( Component )]
+pub struct MapChunk ( IVec2 );
 
-//迷路の3Dオブジェクトをspawnする
+//壁と床のチャンクで共有するマテリアル(チャンクを後からspawnする時に使う)
+#[derive( Resource )]
+pub struct MapMaterials
+{   wall : Handle<StandardMaterial>,
+    floor: Handle<StandardMaterial>,
+}
+
+//テーマを切り換えて法線マップを使う場合に備えてタンジェントを付ける
+fn with_tangents( mut mesh: Mesh ) -> Mesh
+{   if let Err ( err ) = mesh.generate_tangents() { warn!( "{err}" ) }
+    mesh
+}
+
+//迷路の3Dオブジェクトをspawnする(見た目はテーマに従う)
 pub fn spawn_entity
 (   q_entity: Query<Entity, With<MapZeroEntity>>,
     map: Res<Map>,
+    theme: Res<theme::Theme>,
+    assets: Res<load_assets::LoadedAssets>,
     mut cmds: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
@@ -247,48 +361,41 @@ pub fn spawn_entity
 {   //既存のEntityがあれば削除する
     q_entity.for_each( | id | cmds.entity( id ).despawn_recursive() );
 
-    //壁のサイズ、原点の壁のテクスチャ、他の壁のテクスチャ、地面のテクスチャ
-    let size = WALL_CUBE_OBJ3D_SIZE;
-    let texture_wall_zero = WALL_CUBE_OBJ3D_COLOR_ZERO.into();
-    let texture_wall_normal: StandardMaterial = WALL_CUBE_OBJ3D_COLOR.into();
-    let texture_ground = GROUND_PLANE_OBJ3D_COLOR.into();
+    //テクスチャ(壁と床は全チャンクで共有する)
+    let texture_wall_zero = theme.wall_zero.material( &assets );
+    let texture_wall  = materials.add( theme.wall .material( &assets ) );
+    let texture_floor = materials.add( theme.floor.material( &assets ) );
+    let texture_goal  = theme.goal.material( &assets );
+    cmds.insert_resource( MapMaterials { wall: texture_wall.clone(), floor: texture_floor.clone() } );
 
     //迷路をspawnする
-    cmds.spawn( ( PbrBundle::default(), MapZeroEntity ) ) //Cube(親)
-    .insert( meshes.add( shape::Cube::new( size ).into() ) )
+    cmds.spawn( ( PbrBundle::default(), MapZeroEntity, theme::ThemePart::WallZero ) ) //Cube(親)
+    .insert( meshes.add( with_tangents( map.wall_zero_mesh() ) ) )
     .insert( Transform::from_translation( Vec3::ZERO ) ) //原点
     .insert( materials.add( texture_wall_zero ) )
     .with_children
     (   | cmds |
         {   //子は、親からの相対位置にspawnされる(XZ平面)
-            for x in MAP_GRIDS_X_RANGE
-            {    for y in MAP_GRIDS_Y_RANGE
-                {   //原点は親なのでスキップ
-                    if x == 0 && y == 0 { continue }
-
-                    //3D空間の座標
-                    let grid = IVec2::new( x, y );
-                    let vec3 = grid.to_3dxz();
-
-                    //壁
-                    if map.is_wall( grid )
-                    {   cmds.spawn( PbrBundle::default() )
-                        .insert( meshes.add( shape::Cube::new( size ).into() ) )
-                        .insert( Transform::from_translation( vec3 ) )
-                        .insert( materials.add( texture_wall_normal.clone() ) )
-                        ;
-                    }
+            //壁と床はチャンクごとに一つのメッシュにまとめる(頂点はマップの座標のまま)
+            for chunk in map.wall_chunk_iter()
+            {   if let Some ( mesh ) = map.wall_chunk_mesh( chunk )
+                {   cmds.spawn( ( PbrBundle::default(), theme::ThemePart::Wall, MapChunk ( chunk ) ) )
+                    .insert( meshes.add( with_tangents( mesh ) ) )
+                    .insert( texture_wall.clone() )
+                    ;
+                }
+                if let Some ( mesh ) = map.floor_chunk_mesh( chunk )
+                {   cmds.spawn( ( PbrBundle::default(), theme::ThemePart::Floor, MapChunk ( chunk ) ) )
+                    .insert( meshes.add( with_tangents( mesh ) ) )
+                    .insert( texture_floor.clone() )
+                    ;
                 }
             }
 
-            //地面も相対位置でspawnする
-            let long_side = MAP_GRIDS_WIDTH.max( MAP_GRIDS_HEIGHT ) as f32;
-            let half = long_side / 2.0;
-            let position = Vec3::new( half, 0.0, half ) - Vec3::ONE / 2.0;
-            cmds.spawn( PbrBundle::default() )
-            .insert( meshes.add( shape::Plane::from_size( long_side ).into() ) )
-            .insert( Transform::from_translation( position ) )
-            .insert( materials.add( texture_ground ) )
+            //ゴールの目印(頂点はマップの座標のまま)
+            cmds.spawn( ( PbrBundle::default(), theme::ThemePart::Goal ) )
+            .insert( meshes.add( with_tangents( map.goal_mesh() ) ) )
+            .insert( materials.add( texture_goal ) )
             ;
         }
     );
@@ -296,4 +403,21 @@ pub fn spawn_entity
 
 ////////////////////////////////////////////////////////////////////////////////
 
+//フッターを更新する(迷路作成アルゴリズム、ループ化の割合、シード)
+pub fn update_footer
+(   mut q_text: Query<&mut Text, With<init_app::FooterUiLeft>>,
+    map: Res<Map>,
+)
+{   let Ok( mut text ) = q_text.get_single_mut() else { return };
+
+    //難しさに合わせて変えた値には*を付ける
+    let ( generator, braid ) = map.tuned_from().unwrap_or( ( map.generator(), map.braid_percent() ) );
+    let mark = | is_tuned: bool | if is_tuned { "*" } else { "" };
+    text.sections[ init_app::FOOTER_LEFT_GENERATOR ].value = format!( "{}{}", map.generator_name(), mark( generator != map.generator() ) );
+    text.sections[ init_app::FOOTER_LEFT_BRAID     ].value = format!( "{:3}%{}", map.braid_percent(), mark( braid != map.braid_percent() ) );
+    text.sections[ init_app::FOOTER_LEFT_SEED      ].value = map.seed().to_string();
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 //End of code.
\ No newline at end of file
@@ -0,0 +1,261 @@
+use super::*;
+use bevy::{ ecs::component::Tick, utils::HashSet };
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路を一歩ずつ作る状態のResource
+#[derive( Resource )]
+pub struct Digging
+{   digger      : Option<maze::Digger>, //掘っている途中ならSome
+    tick        : Tick,  //作り始めの迷路をMapに入れた時のTick
+    paused      : bool,  //一時停止中か？
+    single_step : bool,  //一時停止中に一歩だけ進めるか？
+    speed       : usize, //MAP_DIG_STEPS_PER_FRAMEの添字
+    is_requested: bool,  //起動時に一歩ずつ作るか？
+    is_dug      : bool,  //このフレームで掘って見た目が変わったか？
+}
+
+impl Default for Digging
+{   fn default() -> Self
+    {   Self
+        {   digger      : None,
+            tick        : Tick::new( 0 ),
+            paused      : false,
+            single_step : false,
+            speed       : MAP_DIG_SPEED_INIT,
+            is_requested: CMDLINE.dig, //コマンドライン引数で指定できる
+            is_dug      : false,
+        }
+    }
+}
+
+//Run Condition
+pub fn is_digging( digging: Res<Digging> ) -> bool { digging.digger.is_some() }
+pub fn is_dug( digging: Res<Digging> ) -> bool { digging.digger.is_some() && digging.is_dug }
+
+//掘っている先頭の目印に印をつけるComponent
+#[derive( Component )]
+pub struct DigHead;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路を一歩ずつ作り始める、または途中ですぐに完成させる(トグル動作)
+pub fn toggle_dig
+(   mut digging: ResMut<Digging>,
+    mut map: ResMut<Map>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //[M]キーの状態
+    let is_key_pressed = inkey.just_pressed( KeyCode::M );
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let button_type = GamepadButtonType::RightTrigger; //ps4[R1]
+    let is_gpdbtn_pressed = gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //入力がないなら(起動時の指定は一度だけ)
+    let is_requested = std::mem::take( &mut digging.is_requested );
+    if ! is_key_pressed && ! is_gpdbtn_pressed && ! is_requested { return }
+
+    //掘っている途中なら残りをまとめて進めて完成させる
+    if let Some ( digger ) = digging.digger.take()
+    {   let is_goal_ok = digger.finish( &mut map );
+        map.warn_goal( is_goal_ok );
+        return
+    }
+
+    //今の設定で迷路を一歩ずつ作り始める(Mapは作り始めの迷路になる)
+    let digger = map.digger();
+    digging.tick = map.last_changed();
+    digging.digger = Some ( digger );
+    digging.paused = false;
+}
+
+//一時停止、一歩だけ進める、速さの切換
+pub fn control_dig
+(   mut digging: ResMut<Digging>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let is_gpdbtn_pressed = | button_type | gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //[P]キーで一時停止と再開
+    if inkey.just_pressed( KeyCode::P ) || is_gpdbtn_pressed( GamepadButtonType::South ) //ps4[×]
+    {   digging.paused = ! digging.paused;
+        info!( "Dig: {}", if digging.paused { "paused" } else { "resumed" } );
+    }
+
+    //[O]キーで一時停止中に一歩だけ進める
+    if digging.paused && inkey.just_pressed( KeyCode::O )
+    {   digging.single_step = true;
+    }
+
+    //[=]キーで速く、[-]キーで遅くする
+    let speed = digging.speed;
+    if inkey.any_just_pressed( [ KeyCode::Equals, KeyCode::NumpadAdd ] )
+    {   digging.speed = ( speed + 1 ).min( MAP_DIG_STEPS_PER_FRAME.len() - 1 );
+    }
+    if inkey.any_just_pressed( [ KeyCode::Minus, KeyCode::NumpadSubtract ] )
+    {   digging.speed = speed.saturating_sub( 1 );
+    }
+    if digging.speed != speed
+    {   info!( "Dig speed: {} steps/frame", MAP_DIG_STEPS_PER_FRAME[ digging.speed ] );
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//迷路を一歩ずつ作り、変わったチャンクだけ3D表示を作り直す
+pub fn advance_dig
+(   mut q_chunk: Query<( Entity, &MapChunk, &theme::ThemePart, &mut Handle<Mesh> )>,
+    q_zero: Query<Entity, With<MapZeroEntity>>,
+    mut digging: ResMut<Digging>,
+    mut map: ResMut<Map>,
+    o_materials: Option<Res<MapMaterials>>,
+    mut cmds: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+)
+{   digging.is_dug = false;
+
+    //他のSystemがMapを作り直したら中断する
+    if map.last_changed() != digging.tick
+    {   digging.digger = None;
+        return
+    }
+
+    //作り始めの迷路が3D表示されるまで待つ
+    if map.is_changed() { return }
+    let Some ( materials ) = o_materials else { return };
+    let Ok ( zero ) = q_zero.get_single() else { return };
+
+    //一時停止中は一歩だけ進める指示を待つ
+    let steps = if ! digging.paused { MAP_DIG_STEPS_PER_FRAME[ digging.speed ] }
+        else if std::mem::take( &mut digging.single_step ) { 1 }
+        else { return };
+
+    //アルゴリズムを進める(Mapが変わったことにすると全体を作り直してしまうので隠す)
+    let Some ( digger ) = digging.digger.as_mut() else { return };
+    let mut dirty = HashSet::new();
+    for _ in 0..steps
+    {   let Some ( changes ) = digger.step( map.bypass_change_detection() ) else { break };
+
+        //行き止りの印は見た目を変えない。壁の側面は隣のマスも変わる
+        for step in changes
+        {   if let maze::DigStep::Backtrack ( _ ) = step { continue }
+            let cell = step.cell();
+            dirty.insert( cell / maze::WALL_CHUNK_GRIDS );
+            for news in NEWS { dirty.insert( ( cell + news ) / maze::WALL_CHUNK_GRIDS ); }
+        }
+    }
+
+    //ゴールまで置いたら全体を作り直す
+    if digger.is_finished()
+    {   let is_goal_ok = digger.is_goal_ok();
+        digging.digger = None;
+        map.set_changed();
+        map.warn_goal( is_goal_ok );
+        return
+    }
+
+    //チャンクだけ作り直すためにMapの変化を隠したので、ミニマップには掘ったことを別に知らせる
+    digging.is_dug = ! dirty.is_empty();
+
+    //変わったチャンクのメッシュを差し替える(面がなくなったら削除する)
+    let chunks = map.wall_chunks();
+    dirty.retain( | c | c.x >= 0 && c.y >= 0 && c.x < chunks.x && c.y < chunks.y );
+    let mut spawned = HashSet::new();
+    for ( id, &MapChunk ( chunk ), &part, mut handle ) in q_chunk.iter_mut()
+    {   if ! dirty.contains( &chunk ) { continue }
+        spawned.insert( ( chunk, matches!( part, theme::ThemePart::Wall ) ) );
+
+        let mesh = match part
+        {   theme::ThemePart::Wall  => map.wall_chunk_mesh ( chunk ),
+            theme::ThemePart::Floor => map.floor_chunk_mesh( chunk ),
+            _ => continue,
+        };
+        match mesh
+        {   Some ( mesh ) => *handle = meshes.add( with_tangents( mesh ) ),
+            None => cmds.entity( id ).despawn_recursive(),
+        }
+    }
+
+    //面ができたチャンクのEntityがまだなければspawnする
+    for chunk in dirty
+    {   let parts =
+        [   ( true , theme::ThemePart::Wall , map.wall_chunk_mesh ( chunk ), &materials.wall  ),
+            ( false, theme::ThemePart::Floor, map.floor_chunk_mesh( chunk ), &materials.floor ),
+        ];
+        for ( is_wall, part, mesh, material ) in parts
+        {   if spawned.contains( &( chunk, is_wall ) ) { continue }
+            let Some ( mesh ) = mesh else { continue };
+
+            let id = cmds.spawn( ( PbrBundle::default(), part, MapChunk ( chunk ) ) )
+            .insert( meshes.add( with_tangents( mesh ) ) )
+            .insert( material.clone() )
+            .id();
+            cmds.entity( zero ).add_child( id );
+        }
+    }
+}
+
+//掘っている先頭に目印を表示する(掘っている間はゴールの目印を隠す)
+pub fn update_dig_head
+(   mut q_head: Query<( &mut Transform, &mut Visibility, &Handle<StandardMaterial> ), With<DigHead>>,
+    mut q_part: Query<( &theme::ThemePart, &mut Visibility ), Without<DigHead>>,
+    digging: Res<Digging>,
+    mut cmds: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+)
+{   //目印がなければspawnする
+    let Ok ( ( mut transform, mut visibility, handle ) ) = q_head.get_single_mut() else
+    {   let size = MAP_DIG_HEAD_SIZE;
+        cmds.spawn( ( PbrBundle::default(), DigHead ) )
+        .insert( meshes.add( shape::Cube::new( size ).into() ) )
+        .insert( materials.add( StandardMaterial::from( MAP_DIG_HEAD_COLOR ) ) )
+        .insert( Visibility::Hidden )
+        ;
+        return
+    };
+
+    //掘っていないなら目印を隠す
+    let Some ( digger ) = &digging.digger else
+    {   *visibility = Visibility::Hidden;
+        return
+    };
+
+    //ゴールはまだ決まっていないので目印を隠す
+    for ( part, mut visibility ) in q_part.iter_mut()
+    {   if let theme::ThemePart::Goal = part { *visibility = Visibility::Hidden }
+    }
+
+    //まだ一歩も進んでいない
+    let Some ( step ) = digger.head() else
+    {   *visibility = Visibility::Hidden;
+        return
+    };
+
+    //先頭の位置と色(後戻りは色を変える)
+    let size = MAP_DIG_HEAD_SIZE;
+    let offset = Vec3::Y * ( maze::WALL_MESH_BOTTOM + size / 2.0 );
+    transform.translation = step.cell().to_3dxz() + offset;
+    *visibility = Visibility::Visible;
+    let color = match step
+    {   maze::DigStep::Backtrack ( _ ) => MAP_DIG_BACKTRACK_COLOR,
+        _ => MAP_DIG_HEAD_COLOR,
+    };
+    if materials.get( handle ).is_some_and( | material | material.base_color != color )
+    {   if let Some ( material ) = materials.get_mut( handle ) { material.base_color = color }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,31 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Map
+{   //OBJ形式のファイルに書き出す(.mtlファイルは拡張子を変えた同じ名前)
+    pub fn write_obj( &self, theme: &theme::Theme, fname: &str ) -> Result<(), String>
+    {   let mtl_path = std::path::Path::new( fname ).with_extension( "mtl" );
+        let mtl_fname = mtl_path.file_name().unwrap_or_default().to_string_lossy();
+
+        let ( obj, mtl ) = self.export_obj( &theme.palette(), &mtl_fname );
+        misc::write_file( fname, &obj )?;
+        misc::write_file( &mtl_path.to_string_lossy(), &mtl )
+    }
+
+    //画像ファイルに書き出す(拡張子が.svgならSVG形式、それ以外はPNG形式)
+    pub fn write_image( &self, theme: &theme::Theme, options: RenderOptions, fname: &str ) -> Result<(), String>
+    {   let palette = theme.palette();
+        let is_svg = fname.to_ascii_lowercase().ends_with( ".svg" );
+        if is_svg
+        {   misc::write_file( fname, self.render_svg( &palette, options ) )
+        }
+        else
+        {   misc::write_file( fname, self.render_png( &palette, options )? )
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,34 @@
+use super::*;
+use bevy::render::{ mesh::Indices, render_resource::PrimitiveTopology };
+
+////////////////////////////////////////////////////////////////////////////////
+
+//頂点データをMeshにする
+fn into_mesh( data: maze::MeshData ) -> Mesh
+{   let mut mesh = Mesh::new( PrimitiveTopology::TriangleList );
+    mesh.insert_attribute( Mesh::ATTRIBUTE_POSITION, data.positions );
+    mesh.insert_attribute( Mesh::ATTRIBUTE_NORMAL  , data.normals   );
+    mesh.insert_attribute( Mesh::ATTRIBUTE_UV_0    , data.uvs       );
+    mesh.set_indices( Some ( Indices::U32( data.indices ) ) );
+
+    mesh
+}
+
+//頂点データをMeshにする(面がなければNone)
+fn into_mesh_if_any( data: maze::MeshData ) -> Option<Mesh>
+{   ( ! data.is_empty() ).then( || into_mesh( data ) )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+impl Map
+{   //3D表示用のMesh(チャンクは面がなければNone)
+    pub fn wall_chunk_mesh ( &self, chunk: IVec2 ) -> Option<Mesh> { into_mesh_if_any( self.wall_chunk_data ( chunk ) ) }
+    pub fn floor_chunk_mesh( &self, chunk: IVec2 ) -> Option<Mesh> { into_mesh_if_any( self.floor_chunk_data( chunk ) ) }
+    pub fn wall_zero_mesh  ( &self ) -> Mesh { into_mesh( self.wall_zero_data() ) }
+    pub fn goal_mesh       ( &self ) -> Mesh { into_mesh( self.goal_data() ) }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
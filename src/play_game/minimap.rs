@@ -0,0 +1,152 @@
+use super::*;
+use map::Map;
+use player::Player;
+use theme::Theme;
+use bevy::render::render_resource::{ Extent3d, TextureDimension, TextureFormat };
+
+////////////////////////////////////////////////////////////////////////////////
+
+//ミニマップのComponent
+#[derive( Component )]
+pub struct MinimapPanel;  //背景
+#[derive( Component )]
+pub struct MinimapImage;  //迷路(1マス1Pixelの画像を拡大する)
+#[derive( Component )]
+pub struct MinimapPlayer; //自キャラの位置
+#[derive( Component )]
+pub struct MinimapView;   //カメラの向き
+
+//ミニマップの設定(壁・床・ゴールの色はテーマに従う)
+const MINIMAP_START_COLOR : Color = Color::LIME_GREEN; //スタート地点の色
+const MINIMAP_PLAYER_COLOR: Color = Color::ORANGE_RED; //自キャラとカメラの向きの色
+const MINIMAP_PLAYER_SIZE : f32 = 8.0;  //自キャラの点の縦横幅(Pixel)
+const MINIMAP_VIEW_LENGTH : f32 = 20.0; //カメラの向きの線の長さ(Pixel)
+const MINIMAP_VIEW_WIDTH  : f32 = 3.0;  //カメラの向きの線の太さ(Pixel)
+
+//表示エリアの中心と縦横幅(Pixel)
+fn panel_rect() -> ( Vec2, Vec2 )
+{   let size = MINIMAP_GRIDS.as_vec2() * PIXELS_PER_GRID;
+    let top_left = MINIMAP_GRID_ZERO.to_screen_pixel() + Vec2::new( -1.0, 1.0 ) * PIXELS_PER_GRID / 2.0;
+    let center = top_left + Vec2::new( size.x, -size.y ) / 2.0;
+    ( center, size )
+}
+
+//1マスあたりのPixel数(マップの長辺を表示エリアに合わせる)
+fn pixels_per_cell( map: &Map ) -> f32
+{   let ( _, size ) = panel_rect();
+    ( size.x / map.width() as f32 ).min( size.y / map.height() as f32 )
+}
+
+//3D空間の座標(XZ平面)からミニマップ上のスクリーン座標を算出する
+fn to_minimap( map: &Map, vec3: Vec3 ) -> Vec2
+{   let ( center, _ ) = panel_rect();
+    let half_w = map.width()  as f32 / 2.0;
+    let half_h = map.height() as f32 / 2.0;
+    let offset = Vec2::new( vec3.x + 0.5 - half_w, half_h - vec3.z - 0.5 ); //マスの中心
+    center + offset * pixels_per_cell( map )
+}
+
+//迷路の画像を作る
+fn minimap_image( map: &Map, theme: &Theme ) -> Image
+{   let mut data = Vec::with_capacity( ( map.width() * map.height() * 4 ) as usize );
+    for y in 0..map.height()
+    {   for x in 0..map.width()
+        {   let cell = IVec2::new( x, y );
+            let color =
+                if      cell == map.start() { MINIMAP_START_COLOR }
+                else if cell == map.goal()  { theme.goal.color    }
+                else if map.is_wall( cell ) { theme.wall.color    }
+                else                        { theme.floor.color   };
+            data.extend( color.as_rgba_u8() );
+        }
+    }
+
+    let size = Extent3d
+    {   width: map.width() as u32,
+        height: map.height() as u32,
+        depth_or_array_layers: 1,
+    };
+    Image::new( size, TextureDimension::D2, data, TextureFormat::Rgba8UnormSrgb )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//ミニマップをspawnする(中身はMapやテーマが変わった時に描く)
+pub fn spawn_minimap
+(   mut cmds: Commands,
+    mut images: ResMut<Assets<Image>>,
+)
+{   let ( center, size ) = panel_rect();
+    let sprite = | color, custom_size | Sprite { color, custom_size: Some ( custom_size ), ..default() };
+    let position = | depth | Transform::from_translation( center.extend( DEPTH_SPRITE_MINIMAP + depth ) );
+
+    cmds.spawn( ( SpriteBundle::default(), MinimapPanel ) )
+    .insert( sprite( Color::BLACK, size ) )
+    .insert( position( 0.0 ) )
+    ;
+    cmds.spawn( ( SpriteBundle::default(), MinimapImage ) )
+    .insert( sprite( Color::WHITE, size ) )
+    .insert( position( 1.0 ) )
+    .insert( images.add( Image::default() ) )
+    ;
+    cmds.spawn( ( SpriteBundle::default(), MinimapPlayer ) )
+    .insert( sprite( MINIMAP_PLAYER_COLOR, Vec2::splat( MINIMAP_PLAYER_SIZE ) ) )
+    .insert( position( 3.0 ) )
+    ;
+    cmds.spawn( ( SpriteBundle::default(), MinimapView ) )
+    .insert( sprite( MINIMAP_PLAYER_COLOR, Vec2::new( MINIMAP_VIEW_LENGTH, MINIMAP_VIEW_WIDTH ) ) )
+    .insert( position( 2.0 ) )
+    ;
+}
+
+//迷路の画像を描き直す(Mapかテーマが変わった時)
+pub fn update_image
+(   mut q_image: Query<( &mut Sprite, &Handle<Image> ), With<MinimapImage>>,
+    mut q_panel: Query<&mut Sprite, ( With<MinimapPanel>, Without<MinimapImage> )>,
+    map: Res<Map>,
+    theme: Res<Theme>,
+    mut images: ResMut<Assets<Image>>,
+)
+{   let Ok ( ( mut sprite, handle ) ) = q_image.get_single_mut() else { return };
+    let Some ( image ) = images.get_mut( handle ) else { return };
+
+    //1マス1Pixelの画像を表示エリアに合わせて拡大する
+    *image = minimap_image( &map, &theme );
+    let size = IVec2::new( map.width(), map.height() ).as_vec2();
+    sprite.custom_size = Some ( size * pixels_per_cell( &map ) );
+
+    //背景はテーマの背景色
+    q_panel.for_each_mut( | mut panel | panel.color = theme.background );
+}
+
+//ミニマップ以外のカメラ
+type WithCamera<T> = ( With<T>, Without<MinimapPlayer>, Without<MinimapView> );
+
+//自キャラの位置とカメラの向きを合わせる
+//＜注意＞ カメラ(俯瞰・一人称とも)の移動後に実行する
+pub fn update_player<T: Component>
+(   mut q_mark: Query<&mut Transform, ( With<MinimapPlayer>, Without<MinimapView> )>,
+    mut q_view: Query<&mut Transform, ( With<MinimapView>, Without<MinimapPlayer> )>,
+    q_camera: Query<&Transform, WithCamera<T>>,
+    q_player: Query<&Player>,
+    map: Res<Map>,
+)
+{   let Ok ( player ) = q_player.get_single() else { return };
+    let Ok ( camera ) = q_camera.get_single() else { return };
+    let Ok ( mut mark ) = q_mark.get_single_mut() else { return };
+    let Ok ( mut view ) = q_view.get_single_mut() else { return };
+
+    //自キャラの位置
+    let vec2 = to_minimap( &map, player.floor_position() );
+    mark.translation = vec2.extend( mark.translation.z );
+
+    //カメラの向き(XZ平面へ投影した向きに線を伸ばす。ミニマップは南が下)
+    let forward = camera.forward();
+    let dir = Vec2::new( forward.x, -forward.z ).normalize_or_zero();
+    view.translation = ( vec2 + dir * MINIMAP_VIEW_LENGTH / 2.0 ).extend( view.translation.z );
+    view.rotation = Quat::from_rotation_z( dir.y.atan2( dir.x ) );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,220 @@
+use super::*;
+use map::Map;
+use std::collections::VecDeque;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//自キャラのComponent
+#[derive( Component )]
+pub struct Player
+{   grid    : IVec2,           //現在のマス(移動中は移動先のマス)
+    from    : IVec2,           //移動元のマス
+    progress: f32,             //移動の進み具合(0.0～1.0、1.0で停止中)
+    route   : VecDeque<IVec2>, //マウスで指定した目的地までの残りの経路
+}
+
+impl Player
+{   fn new( grid: IVec2 ) -> Self
+    {   Self { grid, from: grid, progress: 1.0, route: VecDeque::new() }
+    }
+
+    //現在のマス(移動中は移動先のマス)
+    pub fn grid( &self ) -> IVec2 { self.grid }
+
+    //移動中か？
+    pub fn is_moving( &self ) -> bool { self.progress < 1.0 }
+
+    //隣のマスへの移動を始める
+    pub fn step_to( &mut self, next: IVec2 )
+    {   self.from = self.grid;
+        self.grid = next;
+        self.progress = 0.0;
+    }
+
+    //現在の足元の3D座標(移動中は二つのマスの間を補間する)
+    pub fn floor_position( &self ) -> Vec3
+    {   self.from.to_3dxz().lerp( self.grid.to_3dxz(), self.progress )
+    }
+
+    //板ポリゴンの中心の3D座標
+    fn position( &self ) -> Vec3
+    {   self.floor_position() + PLAYER_OFFSET
+    }
+}
+
+//自キャラの設定
+const PLAYER_SPRITE_SIZE: f32 = 0.8;  //板ポリゴンの縦横幅
+const PLAYER_MOVE_SPEED : f32 = 6.0;  //1秒あたりに進むマス数
+const PLAYER_OFFSET: Vec3 = Vec3::new( 0.0, ( PLAYER_SPRITE_SIZE - 1.0 ) / 2.0, 0.0 ); //地面に立たせる
+const PLAYER_SPRITE_COLOR: Color = Color::WHITE;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//自キャラをspawnする(蟹の板ポリゴン)
+pub fn spawn_player
+(   q_player: Query<Entity, With<Player>>,
+    map: Res<Map>,
+    mut cmds: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_svr: Res<AssetServer>,
+)
+{   //既存のEntityがあれば削除する
+    q_player.for_each( | id | cmds.entity( id ).despawn_recursive() );
+
+    let player = Player::new( map.start() );
+    let material = StandardMaterial
+    {   base_color: PLAYER_SPRITE_COLOR,
+        base_color_texture: Some ( asset_svr.load( ASSETS_SPRITE_KANI_DOTOWN ) ),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    };
+
+    cmds.spawn( PbrBundle::default() )
+    .insert( meshes.add( shape::Quad::new( Vec2::splat( PLAYER_SPRITE_SIZE ) ).into() ) )
+    .insert( Transform::from_translation( player.position() ) )
+    .insert( materials.add( material ) )
+    .insert( player )
+    ;
+}
+
+//Mapが作り直されたら自キャラをスタート地点へ戻す
+pub fn reset_player
+(   mut q_player: Query<&mut Player>,
+    map: Res<Map>,
+)
+{   let Ok ( mut player ) = q_player.get_single_mut() else { return };
+    *player = Player::new( map.start() );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//キー入力・ゲームパッドで自キャラの進む方向を決める
+//（画面の上が奥になるよう、カメラの向きに近い四方へ変換する）
+pub fn catch_input_direction
+(   mut q_player: Query<&mut Player>,
+    o_camera: Option<Res<OrbitCamera>>,
+    map: Res<Map>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   let Ok ( mut player ) = q_player.get_single_mut() else { return };
+    if player.is_moving() { return }
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let is_gpdbtn_pressed = | button_type | gamepads.iter().any
+    (   | gamepad | gpdbtn.pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //画面上の上下左右
+    let mut input = Vec2::ZERO;
+    if inkey.pressed( KeyCode::W ) || is_gpdbtn_pressed( GamepadButtonType::DPadUp    ) { input.y += 1.0 }
+    if inkey.pressed( KeyCode::S ) || is_gpdbtn_pressed( GamepadButtonType::DPadDown  ) { input.y -= 1.0 }
+    if inkey.pressed( KeyCode::D ) || is_gpdbtn_pressed( GamepadButtonType::DPadRight ) { input.x += 1.0 }
+    if inkey.pressed( KeyCode::A ) || is_gpdbtn_pressed( GamepadButtonType::DPadLeft  ) { input.x -= 1.0 }
+    if input == Vec2::ZERO { return }
+
+    //カメラから見た奥と右の方向(XZ平面)
+    let phi = o_camera.map_or( ORBIT_CAMERA_INIT_PHI, | camera | camera.orbit.phi );
+    let forward = Vec2::new( -phi.sin(), -phi.cos() );
+    let right   = Vec2::new( -forward.y, forward.x );
+    let news = nearest_news( forward * input.y + right * input.x );
+
+    //壁でなければ進む(マウスで指定した経路は取り消す)
+    player.route.clear();
+    let next = player.grid + news;
+    if map.is_wall( next ) { return }
+    player.step_to( next );
+}
+
+//XZ平面上のベクトルに最も近い四方
+pub fn nearest_news( vec2: Vec2 ) -> News
+{   if vec2.x.abs() >= vec2.y.abs()
+    {   if vec2.x > 0.0 { News::East } else { News::West }
+    }
+    else
+    {   if vec2.y > 0.0 { News::South } else { News::North }
+    }
+}
+
+//マウスの右クリックで目的地を決め、そこまでの経路を自キャラに設定する
+pub fn catch_input_mouse_click
+(   mut q_player: Query<&mut Player>,
+    q_camera: Query<( &Camera, &GlobalTransform ), With<misc::AppDefault3dCamera>>,
+    q_window: Query<&Window>,
+    map: Res<Map>,
+    mouse_button: Res<Input<MouseButton>>,
+)
+{   if ! mouse_button.just_pressed( MouseButton::Right ) { return }
+    let Ok ( mut player ) = q_player.get_single_mut() else { return };
+    let Ok ( ( camera, camera_transform ) ) = q_camera.get_single() else { return };
+    let Ok ( window ) = q_window.get_single() else { return };
+
+    //カーソルの位置からviewport内の座標を求め、地面(y=-0.5)との交点のマスを目的地にする
+    let Some ( cursor ) = window.cursor_position() else { return };
+    let Some ( viewport ) = camera.logical_viewport_rect() else { return };
+    let Some ( ray ) = camera.viewport_to_world( camera_transform, cursor - viewport.min ) else { return };
+    let ground = Vec3::Y * -0.5;
+    let Some ( distance ) = ray.intersect_plane( ground, Vec3::Y ) else { return };
+    let point = ray.get_point( distance );
+    let goal = IVec2::new( point.x.round() as i32, point.z.round() as i32 );
+
+    //経路を探す(先頭は現在のマスなので除く)
+    let Some ( path ) = map.find_path( player.grid, goal ) else { return };
+    player.route = path.into_iter().skip( 1 ).collect();
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//自キャラを移動する(マスの間を滑らかに動かす)
+pub fn move_player
+(   mut q_player: Query<( &mut Player, &mut Transform )>,
+    map: Res<Map>,
+    time: Res<Time>,
+)
+{   let Ok ( ( mut player, mut transform ) ) = q_player.get_single_mut() else { return };
+
+    //停止中でマウスで指定した経路が残っていれば次のマスへ進む
+    if ! player.is_moving()
+    {   if let Some ( next ) = player.route.pop_front()
+        {   if map.is_wall( next ) { player.route.clear(); return }
+            player.step_to( next );
+        }
+    }
+
+    //移動中なら進める
+    if player.is_moving()
+    {   let time_delta = time.delta().as_secs_f32(); //前回の実行からの経過時間
+        player.progress = ( player.progress + time_delta * PLAYER_MOVE_SPEED ).min( 1.0 );
+    }
+
+    transform.translation = player.position();
+}
+
+//カメラの注視点を自キャラに合わせる
+pub fn follow_player
+(   q_player: Query<&Player>,
+    o_camera: Option<ResMut<OrbitCamera>>,
+)
+{   let Ok ( player ) = q_player.get_single() else { return };
+    let Some ( mut camera ) = o_camera else { return };
+
+    camera.look_at = player.floor_position();
+}
+
+//自キャラの板ポリゴンをカメラへ向ける(ビルボード)
+pub fn face_camera
+(   mut q_player: Query<&mut Transform, With<Player>>,
+    q_camera: Query<&Transform, ( With<misc::AppDefault3dCamera>, Without<Player> )>,
+)
+{   let Ok ( mut transform ) = q_player.get_single_mut() else { return };
+    let Ok ( camera ) = q_camera.get_single() else { return };
+
+    transform.rotation = camera.rotation;
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,185 @@
+use super::*;
+use serde::{ Deserialize, Deserializer, de::Error };
+
+//単体テスト
+#[cfg( test )]
+mod tests;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//組込みのテーマの定義ファイル(WASMでも読めるよう埋め込む)
+const THEMES_BUILTIN: &str = include_str!( "../../assets/themes.ron" );
+
+//迷路の見た目の定義
+#[derive( Resource, Clone, Deserialize )]
+pub struct Theme
+{   pub name      : String,  //テーマ名
+    #[serde( deserialize_with = "deserialize_color" )]
+    pub background: Color,   //背景色
+    pub light     : f32,     //3Dライトの明るさ
+    pub wall      : Surface, //壁
+    pub wall_zero : Surface, //原点の壁
+    pub floor     : Surface, //床
+    pub goal      : Surface, //ゴールの目印
+}
+
+//面の見た目(テクスチャと法線マップは事前ロードしたAssetsのファイル名)
+#[derive( Clone, Deserialize )]
+pub struct Surface
+{   #[serde( deserialize_with = "deserialize_color" )]
+    pub color     : Color,
+    #[serde( default )]
+    pub texture   : Option<String>,
+    #[serde( default )]
+    pub normal_map: Option<String>,
+}
+
+//色を16進数の文字列から読む
+fn deserialize_color<'de, D: Deserializer<'de>>( deserializer: D ) -> Result<Color, D::Error>
+{   let hex = String::deserialize( deserializer )?;
+    Color::hex( &hex ).map_err( | err | D::Error::custom( format!( "\"{hex}\": {err}" ) ) )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//切り換えられるテーマの一覧
+#[derive( Resource )]
+pub struct Themes
+{   list : Vec<Theme>,
+    index: usize,
+}
+
+impl Default for Themes
+{   fn default() -> Self
+    {   //コマンドラインで指定されたファイルを読み、だめなら組込みのテーマを使う
+        let list = CMDLINE.themes.as_ref().and_then
+        (   | fname |
+            misc::read_file( fname )
+            .and_then( | text | parse_themes( &text ) )
+            .map_err( | err | warn!( "Themes \"{fname}\": {err}" ) )
+            .ok()
+        )
+        .unwrap_or_else( || parse_themes( THEMES_BUILTIN ).unwrap() );
+
+        Self { list, index: 0 }
+    }
+}
+
+impl Themes
+{   pub fn current( &self ) -> &Theme { &self.list[ self.index ] }
+}
+
+//テーマの定義を解釈する
+fn parse_themes( text: &str ) -> Result<Vec<Theme>, String>
+{   let list: Vec<Theme> = ron::from_str( text ).map_err( | err | err.to_string() )?;
+    if list.is_empty() { return Err ( "No themes are defined.".to_string() ) }
+    Ok ( list )
+}
+
+impl Theme
+{   //画像と3Dモデルの書き出しに使う色
+    pub fn palette( &self ) -> chip12_maze::Palette
+    {   chip12_maze::Palette
+        {   wall     : self.wall     .color.as_rgba_u8(),
+            wall_zero: self.wall_zero.color.as_rgba_u8(),
+            floor    : self.floor    .color.as_rgba_u8(),
+            goal     : self.goal     .color.as_rgba_u8(),
+        }
+    }
+}
+
+//現在のテーマ(Themesを先に登録しておくこと)
+impl FromWorld for Theme
+{   fn from_world( world: &mut World ) -> Self
+    {   world.resource::<Themes>().current().clone()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//テーマで見た目を決める部位のComponent
+#[derive( Component, Clone, Copy )]
+pub enum ThemePart { Wall, WallZero, Floor, Goal }
+
+impl ThemePart
+{   pub fn surface( self, theme: &Theme ) -> &Surface
+    {   match self
+        {   ThemePart::Wall     => &theme.wall,
+            ThemePart::WallZero => &theme.wall_zero,
+            ThemePart::Floor    => &theme.floor,
+            ThemePart::Goal     => &theme.goal,
+        }
+    }
+}
+
+impl Surface
+{   //StandardMaterialを作る
+    pub fn material( &self, assets: &load_assets::LoadedAssets ) -> StandardMaterial
+    {   let image = | fname: &Option<String> |
+        {   let fname = fname.as_ref()?;
+            let handle = assets.image( fname );
+            if handle.is_none() { warn!( "Theme image \"{fname}\" is not pre-loaded." ) }
+            handle
+        };
+
+        StandardMaterial
+        {   base_color        : self.color,
+            base_color_texture: image( &self.texture ),
+            normal_map_texture: image( &self.normal_map ),
+            ..default()
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//テーマを切り換える
+pub fn change_theme
+(   mut themes: ResMut<Themes>,
+    mut theme: ResMut<Theme>,
+    inkey: Res<Input<KeyCode>>,
+    gpdbtn: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+)
+{   //[T]キーの状態
+    let is_key_pressed = inkey.just_pressed( KeyCode::T );
+
+    //ゲームパッドは抜き挿しでIDが変わるので.iter()で回す
+    let button_type = GamepadButtonType::LeftTrigger; //ps4[L1]
+    let is_gpdbtn_pressed = gamepads.iter().any
+    (   | gamepad | gpdbtn.just_pressed( GamepadButton { gamepad, button_type } )
+    );
+
+    //入力がないなら
+    if ! is_key_pressed && ! is_gpdbtn_pressed { return }
+
+    themes.index = ( themes.index + 1 ) % themes.list.len();
+    *theme = themes.current().clone();
+    info!( "Theme: {}", theme.name );
+}
+
+//テーマが変わったら、迷路を作り直さずに今のEntityの見た目を変える
+pub fn restyle<T: Component>
+(   q_part: Query<( &Handle<StandardMaterial>, &ThemePart )>,
+    mut q_camera: Query<&mut Camera3d, With<T>>,
+    mut q_light: Query<&mut DirectionalLight>,
+    theme: Res<Theme>,
+    assets: Res<load_assets::LoadedAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+)
+{   //マテリアル(壁と床のチャンクは同じハンドルを共有している)
+    for ( handle, &part ) in q_part.iter()
+    {   let Some ( material ) = materials.get_mut( handle ) else { continue };
+        *material = part.surface( &theme ).material( &assets );
+    }
+
+    //背景色とライトの明るさ
+    q_camera.for_each_mut
+    (   | mut camera | camera.clear_color = ClearColorConfig::Custom( theme.background )
+    );
+    q_light.for_each_mut( | mut light | light.illuminance = theme.light );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
@@ -0,0 +1,32 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//組込みのテーマを読める(テクスチャは事前ロード対象のファイルだけ)
+#[test]
+fn builtin_themes_parse()
+{   let list = parse_themes( THEMES_BUILTIN ).unwrap();
+    assert!( list.len() >= 2 );
+
+    for theme in &list
+    {   for surface in [ &theme.wall, &theme.wall_zero, &theme.floor, &theme.goal ]
+        {   for fname in [ &surface.texture, &surface.normal_map ].into_iter().flatten()
+            {   assert!( PRELOAD_ASSETS.contains( &fname.as_str() ), "{} {fname}", theme.name );
+            }
+        }
+    }
+}
+
+//不正な定義はエラーになる
+#[test]
+fn invalid_themes_are_errors()
+{   assert!( parse_themes( "[]" ).is_err() );
+    assert!( parse_themes( "[ ( name: \"X\" ) ]" ).is_err() );
+
+    let bad_color = THEMES_BUILTIN.replacen( "\"FFFFFF\"", "\"WHITE\"", 1 );
+    assert!( parse_themes( &bad_color ).is_err() );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
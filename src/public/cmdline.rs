@@ -0,0 +1,142 @@
+use super::*;
+
+////////////////////////////////////////////////////////////////////////////////
+
+//コマンドライン引数
+//  --width <N>     マップの横幅(Grid)
+//  --height <N>    マップの縦幅(Grid)
+//  --seed <N>      迷路の乱数のシード
+//  --goal-min <N>  スタートからゴールまでの最低歩数
+//  --algorithm <NAME> 迷路作成アルゴリズム(DEPTH-FIRST、PRIM等。DUNGEONは部屋と通路。大文字小文字は区別しない)
+//  --rooms <N|MIN-MAX>     DUNGEONの部屋の数(一つの値か範囲)
+//  --room-size <N|MIN-MAX> DUNGEONの部屋の縦横幅(Grid、奇数に切り上げる)
+//  --difficulty <LEVEL> 迷路の難しさ(EASY、MEDIUM、HARD。シードだけを選び直す)
+//  --tune          難しさの迷路が見つからなければアルゴリズムとループ化の割合も変えてよい(変えたらフッターに*を付ける)
+//  --themes <FILE> テーマの定義ファイル(RON形式。省略時は組込みのテーマ)
+//  --load <FILE>   迷路を作らずに迷路ファイルを読み込む(.ron/.jsonは構造化形式、他はASCII形式)
+//  --export <FILE> ウィンドウを開かずに壁と床の3DモデルをOBJ形式で書き出して終了する
+//  --render <FILE> ウィンドウを開かずに迷路の画像を書き出して終了する(.svgはSVG形式、他はPNG形式)
+//  --solution      画像に解の経路を重ねる
+//  --dead-ends     画像に行き止りの印を重ねる
+//  --print         ウィンドウを開かずに迷路をASCII形式で標準出力に書き出して終了する
+//  --output <FILE> ウィンドウを開かずに迷路ファイルを書き出して終了する(形式は--loadと同じ)
+//  --stats         ウィンドウを開かずに迷路の統計を表示して終了する
+//  --validate <FILE> ウィンドウを開かずに迷路ファイルを検査し、統計を表示して終了する
+//  --dig           起動時に迷路を一歩ずつ作る様子を見せる
+//（--key=value の形式も可。WASMではURLのクエリパラメータ ?key=value&... で指定する）
+#[derive( Default )]
+pub struct CmdLine
+{   pub width   : Option<i32>, //マップの横幅
+    pub height  : Option<i32>, //マップの縦幅
+    pub seed    : Option<u64>, //乱数のシード
+    pub goal_min: Option<u32>, //ゴールまでの最低歩数
+    pub algorithm: Option<String>, //迷路作成アルゴリズムの名前
+    pub rooms    : Option<RangeInclusive<i32>>, //ダンジョンの部屋の数
+    pub room_size: Option<RangeInclusive<i32>>, //ダンジョンの部屋の縦横幅
+    pub difficulty: Option<String>, //迷路の難しさの名前
+    pub tune    : bool,           //難しさに合わせてアルゴリズムとループ化の割合も変えてよいか？
+    pub themes  : Option<String>, //テーマの定義ファイル
+    pub load    : Option<String>, //読み込む迷路ファイル
+    pub export  : Option<String>, //3Dモデルの書き出し先
+    pub render  : Option<String>, //画像の書き出し先
+    pub solution : bool,          //画像に解の経路を重ねるか？
+    pub dead_ends: bool,          //画像に行き止りの印を重ねるか？
+    pub print   : bool,           //迷路をASCII形式で標準出力に書き出すか？
+    pub output  : Option<String>, //迷路ファイルの書き出し先
+    pub stats   : bool,           //迷路の統計を表示するか？
+    pub validate: Option<String>, //検査する迷路ファイル
+    pub dig     : bool,           //起動時に迷路を一歩ずつ作るか？
+    pub errors  : Vec<String>, //解釈できなかった引数
+}
+
+//起動時のコマンドライン引数
+pub static CMDLINE: Lazy<CmdLine> = Lazy::new
+(   || CmdLine::parse( args().into_iter() )
+);
+
+//ネイティブではコマンドライン引数をそのまま使う
+#[cfg( not( target_arch = "wasm32" ) )]
+fn args() -> Vec<String>
+{   std::env::args().skip( 1 ).collect()
+}
+
+//WASMではURLのクエリパラメータを --key=value の形に変換する
+#[cfg( target_arch = "wasm32" )]
+fn args() -> Vec<String>
+{   let search = web_sys::window()
+        .and_then( | window | window.location().search().ok() )
+        .unwrap_or_default();
+
+    search
+    .trim_start_matches( '?' )
+    .split( '&' )
+    .filter( | param | ! param.is_empty() )
+    .map( | param | format!( "--{param}" ) )
+    .collect()
+}
+
+impl CmdLine
+{   //引数の列を解釈する
+    pub fn parse<I: Iterator<Item = String>>( args: I ) -> Self
+    {   let mut cmdline = CmdLine::default();
+        let mut args = args.peekable();
+
+        while let Some ( arg ) = args.next()
+        {   //--key=value と --key value の両方を受け付ける
+            let ( key, value ) = match arg.split_once( '=' )
+            {   Some ( ( key, value ) ) => ( key.to_string(), Some ( value.to_string() ) ),
+                None =>
+                {   let value = args.next_if( | next | ! next.starts_with( "--" ) );
+                    ( arg, value )
+                }
+            };
+
+            match ( key.as_str(), value )
+            {   ( "--width" , Some ( v ) ) => cmdline.width  = cmdline.parse_value( &key, &v ),
+                ( "--height", Some ( v ) ) => cmdline.height = cmdline.parse_value( &key, &v ),
+                ( "--seed"  , Some ( v ) ) => cmdline.seed   = cmdline.parse_value( &key, &v ),
+                ( "--goal-min", Some ( v ) ) => cmdline.goal_min = cmdline.parse_value( &key, &v ),
+                ( "--algorithm", Some ( v ) ) => cmdline.algorithm = Some ( v ),
+                ( "--rooms"    , Some ( v ) ) => cmdline.rooms     = cmdline.parse_range( &key, &v ),
+                ( "--room-size", Some ( v ) ) => cmdline.room_size = cmdline.parse_range( &key, &v ),
+                ( "--difficulty", Some ( v ) ) => cmdline.difficulty = Some ( v ),
+                ( "--themes"  , Some ( v ) ) => cmdline.themes   = Some ( v ),
+                ( "--load"    , Some ( v ) ) => cmdline.load     = Some ( v ),
+                ( "--export"  , Some ( v ) ) => cmdline.export   = Some ( v ),
+                ( "--render"  , Some ( v ) ) => cmdline.render   = Some ( v ),
+                ( "--solution" , None ) => cmdline.solution  = true,
+                ( "--dead-ends", None ) => cmdline.dead_ends = true,
+                ( "--print"    , None ) => cmdline.print     = true,
+                ( "--stats"    , None ) => cmdline.stats     = true,
+                ( "--dig"      , None ) => cmdline.dig       = true,
+                ( "--tune"     , None ) => cmdline.tune      = true,
+                ( "--output"  , Some ( v ) ) => cmdline.output   = Some ( v ),
+                ( "--validate", Some ( v ) ) => cmdline.validate = Some ( v ),
+                ( _, _ ) => cmdline.errors.push( format!( "Unknown argument \"{key}\"" ) ),
+            }
+        }
+
+        cmdline
+    }
+
+    //値を解釈する（失敗したらエラーを記録してNoneを返す）
+    fn parse_value<T: std::str::FromStr>( &mut self, key: &str, value: &str ) -> Option<T>
+    {   let result = value.parse().ok();
+        if result.is_none()
+        {   self.errors.push( format!( "Invalid value \"{value}\" for \"{key}\"" ) );
+        }
+        result
+    }
+
+    //「N」または「MIN-MAX」の範囲を解釈する（失敗したらエラーを記録してNoneを返す）
+    fn parse_range( &mut self, key: &str, value: &str ) -> Option<RangeInclusive<i32>>
+    {   let ( min, max ) = value.split_once( '-' ).unwrap_or( ( value, value ) );
+        let min = self.parse_value( key, min )?;
+        let max = self.parse_value( key, max )?;
+        Some ( min..=max )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+//End of code.
\ No newline at end of file
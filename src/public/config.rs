@@ -24,8 +24,8 @@ pub static MAIN_WINDOW: Lazy<Option<Window>> = Lazy::new
 const _CARGO_TOML_NAME: &str = env!( "CARGO_PKG_NAME"    );
 const _CARGO_TOML_VER : &str = env!( "CARGO_PKG_VERSION" );
 
-const APP_TITLE: &str = _CARGO_TOML_NAME; //アプリタイトル
-const APP_VER  : &str = _CARGO_TOML_VER;  //アプリのバージョン
+pub const APP_TITLE: &str = _CARGO_TOML_NAME; //アプリタイトル
+pub const APP_VER  : &str = _CARGO_TOML_VER;  //アプリのバージョン
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -108,6 +108,7 @@ counted_array!
 pub const DEPTH_SPRITE_DEBUG_GRID : f32 = 999.0; //重なりの最大値
 pub const DEPTH_SPRITE_KANI_DOTOWN: f32 = 900.0;
 pub const DEPTH_SPRITE_GAME_FRAME : f32 = 800.0;
+pub const DEPTH_SPRITE_MINIMAP    : f32 = 810.0; //ミニマップ(+0.0～+3.0を使う)
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -116,11 +117,13 @@ pub const ORBIT_CAMERA_INIT_R    : f32 = 5.0;      //初期値
 pub const ORBIT_CAMERA_INIT_THETA: f32 = PI * 0.6; //初期値(ラジアン) 1.0:天頂、0.5:真横、0.0:真下
 pub const ORBIT_CAMERA_INIT_PHI  : f32 = PI * 1.8; //初期値(ラジアン) 6時方向が0.0で反時計回り
 
-pub const ORBIT_CAMERA_MAX_R    : f32 = 10.0;      //rの最大値
+pub const ORBIT_CAMERA_MAX_R    : f32 = 10.0;      //rの最大値(マップが小さい場合)
 pub const ORBIT_CAMERA_MIN_R    : f32 = 1.0;       //rの最小値
 pub const ORBIT_CAMERA_MAX_THETA: f32 = PI * 0.99; //Θの最大値(ラジアン)
 pub const ORBIT_CAMERA_MIN_THETA: f32 = PI * 0.51; //Θの最小値(ラジアン)
 
+pub const ORBIT_CAMERA_MAX_R_PER_GRID: f32 = 1.0; //rの最大値(マップの長辺1Gridあたり)
+
 //極座標カメラ操作時のマウスの感度調整
 pub const MOUSE_WHEEL_Y_COEF : f32 = 0.1;
 pub const MOUSE_MOTION_Y_COEF: f32 = 0.01;
@@ -135,27 +138,27 @@ pub static SCREEN_FRAME: Lazy<ScreenFrame> = Lazy::new
     {   let design = vec!
         [  //0123456789 123456789 123456789 123456789 12
             "###########################################", //0
-            "#..............................############", //1
-            "#..............................############", //2
-            "#..............................############", //3
-            "#..............................############", //4
-            "#..............................############", //5
-            "#..............................############", //6
-            "#..............................############", //7
-            "#..............................############", //8
-            "#..............................############", //9
-            "#..............................############", //10
+            "#..............................#..........#", //1
+            "#..............................#..........#", //2
+            "#..............................#..........#", //3
+            "#..............................#..........#", //4
+            "#..............................#..........#", //5
+            "#..............................#..........#", //6
+            "#..............................#..........#", //7
+            "#..............................#..........#", //8
+            "#..............................#..........#", //9
+            "#..............................#..........#", //10
             "#..............................############", //11
-            "#..............................############", //12
-            "#..............................############", //13
-            "#..............................############", //14
-            "#..............................############", //15
-            "#..............................############", //16
-            "#..............................############", //17
-            "#..............................############", //18
-            "#..............................############", //19
-            "#..............................############", //20
-            "#..............................############", //21
+            "#..............................#..........#", //12
+            "#..............................#..........#", //13
+            "#..............................#..........#", //14
+            "#..............................#..........#", //15
+            "#..............................#..........#", //16
+            "#..............................#..........#", //17
+            "#..............................#..........#", //18
+            "#..............................#..........#", //19
+            "#..............................#..........#", //20
+            "#..............................#..........#", //21
             "###########################################", //22
             "                                           ", //23
         ]; //0123456789 123456789 123456789 123456789 12
@@ -169,24 +172,42 @@ pub static SCREEN_FRAME: Lazy<ScreenFrame> = Lazy::new
     }
 );
 
+//サイドパネルのミニマップの表示エリア(Grid、左上のマスと縦横幅)
+pub const MINIMAP_GRID_ZERO: IVec2 = IVec2::new( 32, 1 );
+pub const MINIMAP_GRIDS    : IVec2 = IVec2::new( 10, 10 );
+
+//サイドパネルの情報表示エリア(Grid、左上のマス)
+pub const SIDE_PANEL_GRID_ZERO: IVec2 = IVec2::new( 32, 12 );
+
 ////////////////////////////////////////////////////////////////////////////////
 
-//マップ縦横幅(Grid)
+//マップ縦横幅の初期値(Grid)
 pub const MAP_GRIDS_WIDTH : i32 = 100;
 pub const MAP_GRIDS_HEIGHT: i32 = 100;
 
-//マップのレンジ（外壁含む）
-pub const MAP_GRIDS_X_RANGE: Range<i32> = 0..MAP_GRIDS_WIDTH;
-pub const MAP_GRIDS_Y_RANGE: Range<i32> = 0..MAP_GRIDS_HEIGHT;
-
-//外壁を含まないレンジ
-pub const MAP_GRIDS_X_RANGE_INNER: Range<i32> = 1..MAP_GRIDS_WIDTH  - 1;
-pub const MAP_GRIDS_Y_RANGE_INNER: Range<i32> = 1..MAP_GRIDS_HEIGHT - 1;
-
-////////////////////////////////////////////////////////////////////////////////
-
-//四方の配列
-pub const NEWS: [ News; 4 ] = [ News::South, News::East, News::West, News::North ];
+//実行中に切り換えられるマップ縦横幅(Grid)
+pub const MAP_GRIDS_PRESETS: [ ( i32, i32 ); 4 ] =
+[   ( MAP_GRIDS_WIDTH, MAP_GRIDS_HEIGHT ),
+    ( 21, 21 ),
+    ( 51, 31 ),
+    ( 200, 200 ),
+];
+
+//スタートからゴールまでの最低歩数の初期値(0なら制約なし)
+//（縦横幅の上下限と作り直す回数の上限はchip12-mazeクレートにある）
+pub const MAP_GOAL_MIN_DISTANCE: u32 = 0;
+
+//行き止りを解消してループを作る割合[%]（切換の順番、先頭が初期値）
+pub const MAP_BRAID_PERCENTS: [ u32; 5 ] = [ 0, 25, 50, 75, 100 ];
+
+//迷路を一歩ずつ作る速さ(1フレームあたりにアルゴリズムを進める歩数。遅い順)と初期値の添字
+pub const MAP_DIG_STEPS_PER_FRAME: [ usize; 7 ] = [ 1, 2, 5, 10, 50, 200, 1000 ];
+pub const MAP_DIG_SPEED_INIT: usize = 3;
+
+//迷路を一歩ずつ作る時の先頭の目印(大きさと色)
+pub const MAP_DIG_HEAD_SIZE: f32 = 0.6;
+pub const MAP_DIG_HEAD_COLOR     : Color = Color::YELLOW;     //掘っている
+pub const MAP_DIG_BACKTRACK_COLOR: Color = Color::ORANGE_RED; //後戻りしている
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -8,6 +8,13 @@ pub const WASM : fn() -> bool = || cfg!( target_arch = "wasm32" );
 
 ////////////////////////////////////////////////////////////////////////////////
 
+//コマンドライン引数の解釈に失敗したものをログに出す
+pub fn warn_cmdline_errors()
+{   CMDLINE.errors.iter().for_each( | error | warn!( "{error}" ) );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 //2D cameraをspawnする
 pub fn spawn_2d_camera( mut cmds: Commands )
 {   cmds.spawn( Camera2dBundle::default() )
@@ -96,6 +103,26 @@ pub fn toggle_window_mode
 
 ////////////////////////////////////////////////////////////////////////////////
 
+//ファイルを読み書きする(WASMではファイルを扱えない)
+#[cfg( not( target_arch = "wasm32" ) )]
+pub fn read_file( fname: &str ) -> Result<String, String>
+{   std::fs::read_to_string( fname ).map_err( | err | err.to_string() )
+}
+#[cfg( not( target_arch = "wasm32" ) )]
+pub fn write_file( fname: &str, data: impl AsRef<[ u8 ]> ) -> Result<(), String>
+{   std::fs::write( fname, data ).map_err( | err | err.to_string() )
+}
+#[cfg( target_arch = "wasm32" )]
+pub fn read_file( _fname: &str ) -> Result<String, String>
+{   Err ( "Reading files is not supported on WASM.".to_string() )
+}
+#[cfg( target_arch = "wasm32" )]
+pub fn write_file( _fname: &str, _data: impl AsRef<[ u8 ]> ) -> Result<(), String>
+{   Err ( "Writing files is not supported on WASM.".to_string() )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
 //QueryしたEnityを再帰的に削除する
 pub fn despawn<T: Component>
 (   q_entity: Query<Entity, With<T>>,
@@ -115,6 +142,7 @@ pub fn catch_input_gamepad
     gamepads: Res<Gamepads>,
 )
 {   let Some ( mut camera ) = o_camera else { return };
+    let max_r = camera.max_r;
     let orbit = &mut camera.orbit;
     let time_delta = time.delta().as_secs_f32(); //前回の実行からの経過時間
 
@@ -133,7 +161,7 @@ pub fn catch_input_gamepad
         let button = GamepadButton { gamepad, button_type };
         if let Some ( value ) = axis_button.get( button )
         {   orbit.r += value * time_delta;
-            orbit.r = orbit.r.min( ORBIT_CAMERA_MAX_R );
+            orbit.r = orbit.r.min( max_r );
         }
 
         //左スティックのＹ軸で上下首振り
@@ -167,13 +195,14 @@ pub fn catch_input_mouse
     mut e_mouse_wheel: EventReader<mouse::MouseWheel>,
 )
 {   let Some ( mut camera ) = o_camera else { return };
+    let max_r = camera.max_r;
     let orbit = &mut camera.orbit;
 
     //ホイール
     for mouse_wheel in e_mouse_wheel.iter()
     {   orbit.r += mouse_wheel.y * MOUSE_WHEEL_Y_COEF; //感度良すぎるので
         orbit.r = orbit.r
-            .min( ORBIT_CAMERA_MAX_R )
+            .min( max_r )
             .max( ORBIT_CAMERA_MIN_R );
     }
 
@@ -204,13 +233,14 @@ pub fn catch_input_keyboard
     inkey: Res<Input<KeyCode>>,
 )
 {   let Some ( mut camera ) = o_camera else { return };
+    let max_r = camera.max_r;
     let orbit = &mut camera.orbit;
     let time_delta = time.delta().as_secs_f32(); //前回の実行からの経過時間
 
     for keycode in inkey.get_pressed()
     {   match keycode
         {   KeyCode::X =>
-                orbit.r = ( orbit.r + time_delta ).min( ORBIT_CAMERA_MAX_R ),
+                orbit.r = ( orbit.r + time_delta ).min( max_r ),
             KeyCode::Z =>
                 orbit.r = ( orbit.r - time_delta ).max( ORBIT_CAMERA_MIN_R ),
             KeyCode::Up =>
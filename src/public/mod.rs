@@ -8,6 +8,10 @@ pub use config::*;
 mod types;
 pub use types::*;
 
+//コマンドライン引数
+mod cmdline;
+pub use cmdline::*;
+
 //ユーティリティ
 pub mod misc;
 
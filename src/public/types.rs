@@ -60,6 +60,7 @@ impl Orbit
 pub struct OrbitCamera
 {   pub orbit: Orbit,  //カメラ自身の極座標
     pub look_at: Vec3, //カメラの注視点の直交座標
+    pub max_r: f32,    //rの最大値(マップの大きさに合わせる)
 }
 
 impl Default for OrbitCamera
@@ -71,6 +72,7 @@ impl Default for OrbitCamera
                 phi  : ORBIT_CAMERA_INIT_PHI,
             },
             look_at: Vec3::ZERO,
+            max_r  : ORBIT_CAMERA_MAX_R,
         }
     }
 }
@@ -104,23 +106,8 @@ pub struct ScreenFrame<'a>
 
 ////////////////////////////////////////////////////////////////////////////////
 
-//四方を表す列挙型
-#[derive( Default, Clone, Copy )]
-pub enum News { #[default] North, East, West, South }
-
-//IVec2 = IVec2 + News
-impl Add<News> for IVec2
-{   type Output = IVec2;
-    fn add( mut self, news: News ) -> IVec2
-    {   match news
-        {   News::North => { self.y -= 1; }
-            News::East  => { self.x += 1; }
-            News::West  => { self.x -= 1; }
-            News::South => { self.y += 1; }
-        }
-        self
-    }
-}
+//四方を表す列挙型と四方の配列(迷路のクレートと共用する)
+pub use chip12_maze::{ News, NEWS };
 
 ////////////////////////////////////////////////////////////////////////////////
 